@@ -25,6 +25,12 @@ pin_project! {
 	}
 }
 
+impl<FUTURE> AwaitAll<FUTURE> {
+	pub(crate) fn is_finished(&self, index: usize) -> bool {
+		self.completed_indices.contains(&index)
+	}
+}
+
 impl<FUTURE> Future for AwaitAll<FUTURE>
 where
 	FUTURE: Future,
@@ -81,5 +87,7 @@ mod test {
 			poll_once(await_all.as_mut()).await.is_none(),
 			"Future should have been pending"
 		);
+		assert!(await_all.is_finished(0), "First future should have finished");
+		assert!(!await_all.is_finished(1), "Second future should still be pending");
 	}
 }
@@ -0,0 +1,55 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub(crate) const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Whole seconds since the unix epoch, rounded down (so negative before the epoch),
+/// and the remaining nanoseconds.
+pub(crate) fn unix_time(system_time: SystemTime) -> (i64, u32) {
+	match system_time.duration_since(UNIX_EPOCH) {
+		Ok(since_epoch) => (since_epoch.as_secs() as i64, since_epoch.subsec_nanos()),
+		Err(error) => {
+			let before_epoch = error.duration();
+			match before_epoch.subsec_nanos() {
+				0 => (-(before_epoch.as_secs() as i64), 0),
+				nanoseconds => (-(before_epoch.as_secs() as i64) - 1, 1_000_000_000 - nanoseconds),
+			}
+		}
+	}
+}
+
+/// Inverse of [`unix_time`] for whole seconds.
+pub(crate) fn system_time_from_unix_time(seconds: i64) -> SystemTime {
+	let duration = Duration::from_secs(seconds.unsigned_abs());
+	if seconds < 0 {
+		UNIX_EPOCH - duration
+	} else {
+		UNIX_EPOCH + duration
+	}
+}
+
+/// Converts days since the unix epoch into a (year, month, day) date of the proleptic gregorian calendar.
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+	let days = days + 719_468;
+	let era = days.div_euclid(146_097);
+	let day_of_era = days.rem_euclid(146_097);
+	let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let shifted_month = (5 * day_of_year + 2) / 153;
+	let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+	let month = if shifted_month < 10 {
+		shifted_month + 3
+	} else {
+		shifted_month - 9
+	} as u32;
+	let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+	(year, month, day)
+}
+
+/// Day of the week of the given days since the unix epoch, starting with 0 for sunday.
+pub(crate) fn weekday_from_days(days: i64) -> u32 {
+	// the unix epoch was a thursday
+	(days + 4).rem_euclid(7) as u32
+}
@@ -0,0 +1,241 @@
+use crate::calendar::{civil_from_days, system_time_from_unix_time, unix_time, weekday_from_days, SECONDS_PER_DAY};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::SystemTime;
+
+/// Calendar rule in cron syntax that describes at which system times something should happen, all in UTC.
+///
+/// The expression consists of six fields separated by whitespace:
+/// `second minute hour day-of-month month day-of-week`, e.g. `0 */5 * * * *` for every five minutes
+/// or `0 0 2 * * 1-5` for 02:00 on weekdays. Every field supports `*`, single values (`5`), ranges (`1-5`),
+/// steps (`*/15`, `10-50/20`) and lists of those (`0,30`). The day of week goes from 0 (sunday) to 7 (sunday again).
+///
+/// Like in cron, if both day of month and day of week are restricted (don't start with `*`),
+/// a day matches if either of them matches.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CronSchedule {
+	seconds: u64,
+	minutes: u64,
+	hours: u64,
+	days_of_month: u64,
+	months: u64,
+	days_of_week: u64,
+	day_of_month_restricted: bool,
+	day_of_week_restricted: bool,
+}
+
+/// Error returned when parsing a [`CronSchedule`] from an invalid expression.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidCronSchedule {
+	expression: String,
+	reason: String,
+}
+
+impl Display for InvalidCronSchedule {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(
+			formatter,
+			"Invalid cron expression {:?}: {}",
+			self.expression, self.reason
+		)
+	}
+}
+
+impl Error for InvalidCronSchedule {}
+
+impl FromStr for CronSchedule {
+	type Err = InvalidCronSchedule;
+
+	fn from_str(expression: &str) -> Result<Self, Self::Err> {
+		let invalid = |reason: String| InvalidCronSchedule {
+			expression: expression.to_owned(),
+			reason,
+		};
+
+		let fields = expression.split_whitespace().collect::<Vec<_>>();
+		let [seconds, minutes, hours, days_of_month, months, days_of_week] = fields[..] else {
+			return Err(invalid(format!("expected 6 fields, got {}", fields.len())));
+		};
+
+		let mut days_of_week = parse_field(days_of_week, 0, 7).map_err(invalid)?;
+		// both 0 and 7 are sunday
+		if days_of_week & (1 << 7) != 0 {
+			days_of_week = (days_of_week | 1) & !(1 << 7);
+		}
+
+		Ok(Self {
+			seconds: parse_field(seconds, 0, 59).map_err(invalid)?,
+			minutes: parse_field(minutes, 0, 59).map_err(invalid)?,
+			hours: parse_field(hours, 0, 23).map_err(invalid)?,
+			days_of_month: parse_field(days_of_month, 1, 31).map_err(invalid)?,
+			months: parse_field(months, 1, 12).map_err(invalid)?,
+			days_of_week,
+			day_of_month_restricted: !days_of_month.starts_with('*'),
+			day_of_week_restricted: !fields[5].starts_with('*'),
+		})
+	}
+}
+
+/// Parses one field into a bit set of the allowed values.
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+	let parse_value = |value: &str| match value.parse::<u32>() {
+		Ok(value) if (min..=max).contains(&value) => Ok(value),
+		_ => Err(format!("{value:?} is not a number between {min} and {max}")),
+	};
+
+	let mut allowed = 0;
+	for part in field.split(',') {
+		let (range, step) = match part.split_once('/') {
+			Some((range, step)) => match step.parse::<u32>() {
+				Ok(step) if step > 0 => (range, step),
+				_ => return Err(format!("{step:?} is not a valid step")),
+			},
+			None => (part, 1),
+		};
+		let (start, end) = match range {
+			"*" => (min, max),
+			range => match range.split_once('-') {
+				Some((start, end)) => (parse_value(start)?, parse_value(end)?),
+				None => {
+					let value = parse_value(range)?;
+					// `5/10` means every 10 starting at 5
+					(value, if part.contains('/') { max } else { value })
+				}
+			},
+		};
+		if start > end {
+			return Err(format!("{range:?} is an empty range"));
+		}
+		for value in (start..=end).step_by(step as usize) {
+			allowed |= 1 << value;
+		}
+	}
+	Ok(allowed)
+}
+
+impl CronSchedule {
+	/// The first system time strictly after `after` that matches this schedule,
+	/// or `None` if the schedule never matches (e.g. on february 30th).
+	pub fn next_after(&self, after: SystemTime) -> Option<SystemTime> {
+		// If nothing matches within a full cycle of the gregorian calendar, nothing matches ever.
+		const DAYS_IN_400_YEARS: i64 = 146_097;
+
+		let (seconds, _) = unix_time(after);
+		let first = seconds + 1;
+		let first_day = first.div_euclid(SECONDS_PER_DAY);
+		let first_second_of_day = first.rem_euclid(SECONDS_PER_DAY) as u32;
+
+		(first_day..=first_day + DAYS_IN_400_YEARS)
+			.filter(|&day| self.matches_day(day))
+			.find_map(|day| {
+				let earliest_second = if day == first_day { first_second_of_day } else { 0 };
+				self.first_second_of_day_from(earliest_second)
+					.map(|second_of_day| day * SECONDS_PER_DAY + i64::from(second_of_day))
+			})
+			.map(system_time_from_unix_time)
+	}
+
+	fn matches_day(&self, days_since_epoch: i64) -> bool {
+		let (_, month, day) = civil_from_days(days_since_epoch);
+		if !contains(self.months, month) {
+			return false;
+		}
+
+		let day_of_month_matches = contains(self.days_of_month, day);
+		let day_of_week_matches = contains(self.days_of_week, weekday_from_days(days_since_epoch));
+		match (self.day_of_month_restricted, self.day_of_week_restricted) {
+			(true, true) => day_of_month_matches || day_of_week_matches,
+			_ => day_of_month_matches && day_of_week_matches,
+		}
+	}
+
+	fn first_second_of_day_from(&self, earliest_second: u32) -> Option<u32> {
+		(0..24)
+			.filter(|&hour| contains(self.hours, hour))
+			.flat_map(|hour| (0..60).map(move |minute| (hour, minute)))
+			.filter(|&(_, minute)| contains(self.minutes, minute))
+			.flat_map(|(hour, minute)| (0..60).map(move |second| (hour, minute, second)))
+			.filter(|&(_, _, second)| contains(self.seconds, second))
+			.map(|(hour, minute, second)| hour * 3600 + minute * 60 + second)
+			.find(|&second_of_day| second_of_day >= earliest_second)
+	}
+}
+
+fn contains(allowed: u64, value: u32) -> bool {
+	allowed & (1 << value) != 0
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::rfc3339::Rfc3339;
+	use std::time::{Duration, UNIX_EPOCH};
+
+	#[test]
+	fn should_find_the_next_match_within_the_same_hour() {
+		let schedule = "0 */5 * * * *".parse::<CronSchedule>().expect("Failed to parse");
+
+		assert_eq!("1970-01-01T00:05:00.000000Z", next_after(&schedule, UNIX_EPOCH));
+		assert_eq!(
+			"1970-01-01T00:10:00.000000Z",
+			next_after(&schedule, UNIX_EPOCH + Duration::from_secs(5 * 60))
+		);
+	}
+
+	#[test]
+	fn should_cross_month_and_year_boundaries() {
+		let schedule = "0 0 2 1 * *".parse::<CronSchedule>().expect("Failed to parse");
+		// 2023-12-15T00:00:00Z
+		let after = UNIX_EPOCH + Duration::from_secs(1_702_598_400);
+
+		assert_eq!("2024-01-01T02:00:00.000000Z", next_after(&schedule, after));
+	}
+
+	#[test]
+	fn should_find_leap_days() {
+		let schedule = "0 0 0 29 2 *".parse::<CronSchedule>().expect("Failed to parse");
+		// 2021-01-01T00:00:00Z
+		let after = UNIX_EPOCH + Duration::from_secs(1_609_459_200);
+
+		assert_eq!("2024-02-29T00:00:00.000000Z", next_after(&schedule, after));
+	}
+
+	#[test]
+	fn should_match_either_day_of_month_or_day_of_week_if_both_are_restricted() {
+		// on the 10th and on sundays, 1970-01-04 was a sunday
+		let schedule = "0 0 0 10 * 7".parse::<CronSchedule>().expect("Failed to parse");
+
+		assert_eq!("1970-01-04T00:00:00.000000Z", next_after(&schedule, UNIX_EPOCH));
+		let after = UNIX_EPOCH + Duration::from_secs(5 * 86_400);
+		assert_eq!("1970-01-10T00:00:00.000000Z", next_after(&schedule, after));
+	}
+
+	#[test]
+	fn should_never_match_impossible_dates() {
+		let schedule = "0 0 0 30 2 *".parse::<CronSchedule>().expect("Failed to parse");
+
+		assert_eq!(None, schedule.next_after(UNIX_EPOCH));
+	}
+
+	#[test]
+	fn should_reject_invalid_expressions() {
+		for expression in [
+			"* * * * *",
+			"60 * * * * *",
+			"* * * 0 * *",
+			"*/0 * * * * *",
+			"5-1 * * * * *",
+			"a * * * * *",
+		] {
+			assert!(
+				expression.parse::<CronSchedule>().is_err(),
+				"{expression:?} should have been rejected"
+			);
+		}
+	}
+
+	fn next_after(schedule: &CronSchedule, after: SystemTime) -> String {
+		Rfc3339(schedule.next_after(after).expect("Schedule didn't match")).to_string()
+	}
+}
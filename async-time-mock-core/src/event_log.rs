@@ -0,0 +1,123 @@
+use crate::rfc3339::Rfc3339;
+use crate::{Instant, PendingTimer};
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::time::SystemTime;
+
+/// Something that happened in a [`TimerRegistry`](crate::TimerRegistry),
+/// see [`TimerRegistry::set_event_log_capacity`](crate::TimerRegistry::set_event_log_capacity).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TimerEvent {
+	/// A timer has been scheduled.
+	Scheduled(PendingTimer),
+	/// A timer has expired and has been started.
+	Triggered(PendingTimer),
+	/// The [`TimeHandlerGuard`](crate::TimeHandlerGuard) of a started timer has been dropped.
+	HandlerFinished(PendingTimer),
+	/// The current time has been moved forward.
+	TimeAdvanced { from: Instant, to: Instant },
+	/// The system time has jumped without the monotonic time being advanced.
+	SystemTimeJumped { from: SystemTime, to: SystemTime },
+}
+
+impl Display for TimerEvent {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+		use TimerEvent::*;
+		match self {
+			Scheduled(timer) => write!(formatter, "scheduled: {timer}"),
+			Triggered(timer) => write!(formatter, "triggered: {timer}"),
+			HandlerFinished(timer) => write!(formatter, "handler finished: {timer}"),
+			TimeAdvanced { from, to } => write!(
+				formatter,
+				"time advanced: {:?} -> {:?}",
+				from.as_duration(),
+				to.as_duration()
+			),
+			SystemTimeJumped { from, to } => {
+				write!(formatter, "system time jumped: {} -> {}", Rfc3339(*from), Rfc3339(*to))
+			}
+		}
+	}
+}
+
+/// Keeps the latest events up to a given capacity, doesn't record anything without a capacity.
+#[derive(Debug, Default)]
+pub(crate) struct EventLog {
+	capacity: Option<usize>,
+	events: VecDeque<TimerEvent>,
+}
+
+impl EventLog {
+	pub(crate) fn set_capacity(&mut self, capacity: Option<usize>) {
+		self.capacity = capacity;
+		let retained_events = capacity.unwrap_or_default();
+		while self.events.len() > retained_events {
+			self.events.pop_front();
+		}
+	}
+
+	/// Only creates the event if it is actually recorded.
+	pub(crate) fn record(&mut self, event: impl FnOnce() -> TimerEvent) {
+		let Some(capacity) = self.capacity.filter(|&capacity| capacity > 0) else {
+			return;
+		};
+
+		if self.events.len() == capacity {
+			self.events.pop_front();
+		}
+		self.events.push_back(event());
+	}
+
+	pub(crate) fn events(&self) -> Vec<TimerEvent> {
+		self.events.iter().cloned().collect()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use std::time::Duration;
+
+	#[test]
+	fn should_not_record_events_without_capacity() {
+		let mut event_log = EventLog::default();
+
+		event_log.record(|| time_advanced(1));
+
+		assert!(event_log.events().is_empty());
+	}
+
+	#[test]
+	fn should_only_keep_the_latest_events() {
+		let mut event_log = EventLog::default();
+		event_log.set_capacity(Some(2));
+
+		for seconds in 1..=3 {
+			event_log.record(|| time_advanced(seconds));
+		}
+
+		assert_eq!(vec![time_advanced(2), time_advanced(3)], event_log.events());
+	}
+
+	#[test]
+	fn should_drop_old_events_when_reducing_the_capacity() {
+		let mut event_log = EventLog::default();
+		event_log.set_capacity(Some(3));
+		for seconds in 1..=3 {
+			event_log.record(|| time_advanced(seconds));
+		}
+
+		event_log.set_capacity(Some(1));
+		assert_eq!(vec![time_advanced(3)], event_log.events());
+
+		event_log.set_capacity(None);
+		assert!(event_log.events().is_empty());
+	}
+
+	fn time_advanced(seconds: u64) -> TimerEvent {
+		TimerEvent::TimeAdvanced {
+			from: Instant::new(Duration::ZERO, 0),
+			to: Instant::new(Duration::from_secs(seconds), 0),
+		}
+	}
+}
@@ -0,0 +1,24 @@
+use crate::rfc3339::Rfc3339;
+use crate::TimerRegistry;
+use std::sync::Arc;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::FormatTime;
+
+/// Formats the mocked [`TimerRegistry::system_time`] the same way as [`tracing_subscriber::fmt::time::SystemTime`]
+/// formats the real one, e.g. `1970-01-01T00:00:01.000000Z`.
+impl FormatTime for TimerRegistry {
+	fn format_time(&self, writer: &mut Writer<'_>) -> std::fmt::Result {
+		write!(writer, "{}", Rfc3339(self.system_time()))
+	}
+}
+
+/// [`FormatTime`] for a shared [`TimerRegistry`], because a subscriber needs to own its timer,
+/// e.g. `tracing_subscriber::fmt().with_timer(MockFormatTime(timer_registry.clone()))`.
+#[derive(Clone, Debug)]
+pub struct MockFormatTime(pub Arc<TimerRegistry>);
+
+impl FormatTime for MockFormatTime {
+	fn format_time(&self, writer: &mut Writer<'_>) -> std::fmt::Result {
+		self.0.format_time(writer)
+	}
+}
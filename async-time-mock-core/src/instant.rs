@@ -23,6 +23,11 @@ impl Instant {
 		self.duration
 	}
 
+	/// Only meant for diagnostics, use [`into_duration`](Self::into_duration) for calculations.
+	pub(crate) const fn as_duration(&self) -> Duration {
+		self.duration
+	}
+
 	// std::time::Instant::now() isn't supported because it would require a TimerRegistry
 
 	/// Equivalent to [`std::time::Instant::duration_since`].
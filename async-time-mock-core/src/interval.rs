@@ -1,3 +1,4 @@
+use crate::timer::TimerKind;
 use crate::{Instant, TimeHandlerGuard, TimerRegistry};
 use std::fmt::{Debug, Formatter};
 use std::future::{poll_fn, Future};
@@ -11,16 +12,57 @@ pub struct Interval {
 	sleep: Pin<Box<dyn Future<Output = TimeHandlerGuard> + Send>>,
 	next_deadline: Instant,
 	period: Duration,
+	missed_tick_behavior: MissedTickBehavior,
+	missed_ticks: u64,
+	label: Option<Arc<str>>,
+}
+
+/// Defines how an [`Interval`] behaves once a tick has been delivered after the next tick should have happened,
+/// equivalent to `tokio::time::MissedTickBehavior`.
+///
+/// Mock timers are only late if they are scheduled in the past, e.g. by [`TimerRegistry::interval_at`]
+/// with a start before the current time.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MissedTickBehavior {
+	/// Ticks as fast as possible until caught up, so the ticks keep being aligned to the start.
+	#[default]
+	Burst,
+	/// Ticks one period after the late tick has been delivered, so all following ticks are shifted.
+	Delay,
+	/// Skips the missed ticks and ticks at the next multiple of the period after the start.
+	Skip,
+}
+
+impl MissedTickBehavior {
+	fn next_deadline(self, missed_deadline: Instant, now: Instant, period: Duration) -> Instant {
+		use MissedTickBehavior::*;
+		match self {
+			Burst => missed_deadline + period,
+			Delay => now + period,
+			Skip => {
+				let nanoseconds_since_aligned = (now - missed_deadline).as_nanos() % period.as_nanos();
+				now + period - Duration::from_nanos(nanoseconds_since_aligned as u64)
+			}
+		}
+	}
 }
 
 impl Interval {
-	pub(crate) fn new(timer_registry: Arc<TimerRegistry>, start: Instant, period: Duration) -> Self {
-		let sleep = Box::pin(timer_registry.sleep_until(start));
+	pub(crate) fn new(
+		timer_registry: Arc<TimerRegistry>,
+		start: Instant,
+		period: Duration,
+		label: Option<Arc<str>>,
+	) -> Self {
+		let sleep = Box::pin(timer_registry.schedule_at(start, TimerKind::Interval, label.clone()));
 		Self {
 			timer_registry,
 			sleep,
 			next_deadline: start,
 			period,
+			missed_tick_behavior: MissedTickBehavior::default(),
+			missed_ticks: 0,
+			label,
 		}
 	}
 
@@ -33,22 +75,84 @@ impl Interval {
 
 		let tick_time = self.next_deadline;
 
-		self.next_deadline = tick_time + self.period;
+		let now = self.timer_registry.now();
+		self.next_deadline = if now > tick_time {
+			self.missed_ticks += 1;
+			if self.missed_tick_behavior != MissedTickBehavior::Burst {
+				// the following deadlines that have already passed won't be ticked at
+				self.missed_ticks += ((now - tick_time).as_nanos() / self.period.as_nanos()) as u64;
+			}
+			self.missed_tick_behavior.next_deadline(tick_time, now, self.period)
+		} else {
+			tick_time + self.period
+		};
 
-		self.sleep = Box::pin(self.timer_registry.sleep_until(self.next_deadline));
+		self.sleep = self.sleep_until_next_deadline();
 
 		Poll::Ready((guard, tick_time))
 	}
 
+	/// Resets the interval to tick one period after the current time.
 	pub fn reset(&mut self) {
-		let now = self.timer_registry.now();
-		self.next_deadline = now + self.period;
-		self.sleep = Box::pin(self.timer_registry.sleep_until(self.next_deadline));
+		self.reset_after(self.period);
+	}
+
+	/// Resets the interval to tick at the current time, so the next tick happens the next time the time is advanced.
+	pub fn reset_immediately(&mut self) {
+		self.reset_at(self.timer_registry.now());
+	}
+
+	/// Resets the interval to tick `after` the current time.
+	pub fn reset_after(&mut self, after: Duration) {
+		self.reset_at(self.timer_registry.now() + after);
+	}
+
+	/// Resets the interval to tick at `deadline`, the ticks after it are one period apart.
+	///
+	/// # Panics
+	/// When `deadline` was created by a different instance of `TimerRegistry`.
+	pub fn reset_at(&mut self, deadline: Instant) {
+		self.next_deadline = deadline;
+		self.sleep = self.sleep_until_next_deadline();
+	}
+
+	/// The [`TimerRegistry`] this interval is scheduled in.
+	pub fn timer_registry(&self) -> &Arc<TimerRegistry> {
+		&self.timer_registry
 	}
 
 	pub fn period(&self) -> Duration {
 		self.period
 	}
+
+	/// Point in time the next tick is scheduled at.
+	pub fn next_deadline(&self) -> Instant {
+		self.next_deadline
+	}
+
+	/// Number of ticks that didn't happen at their deadline, either because they were delivered late
+	/// or because they have been left out according to the [`MissedTickBehavior`].
+	///
+	/// Use this together with [`next_deadline`](Self::next_deadline) to observe how much an interval lags behind.
+	pub fn missed_ticks(&self) -> u64 {
+		self.missed_ticks
+	}
+
+	pub fn missed_tick_behavior(&self) -> MissedTickBehavior {
+		self.missed_tick_behavior
+	}
+
+	/// Sets how to continue once a tick is late, see [`MissedTickBehavior`]. Defaults to [`MissedTickBehavior::Burst`].
+	pub fn set_missed_tick_behavior(&mut self, missed_tick_behavior: MissedTickBehavior) {
+		self.missed_tick_behavior = missed_tick_behavior;
+	}
+
+	fn sleep_until_next_deadline(&self) -> Pin<Box<dyn Future<Output = TimeHandlerGuard> + Send>> {
+		Box::pin(
+			self.timer_registry
+				.schedule_at(self.next_deadline, TimerKind::Interval, self.label.clone()),
+		)
+	}
 }
 
 impl Debug for Interval {
@@ -58,6 +162,9 @@ impl Debug for Interval {
 			sleep: _,
 			next_deadline,
 			period,
+			missed_tick_behavior,
+			missed_ticks,
+			label,
 		} = self;
 		formatter
 			.debug_struct("Interval")
@@ -65,6 +172,9 @@ impl Debug for Interval {
 			.field("sleep", &"impl Future<Output = TimeHandlerGuard>")
 			.field("next_deadline", next_deadline)
 			.field("period", period)
+			.field("missed_tick_behavior", missed_tick_behavior)
+			.field("missed_ticks", missed_ticks)
+			.field("label", label)
 			.finish()
 	}
 }
@@ -1,14 +1,26 @@
 mod await_all;
+mod calendar;
+mod cron_schedule;
+mod event_log;
+#[cfg(feature = "tracing-subscriber")]
+mod format_time;
 mod instant;
 mod interval;
+mod real_time;
+mod rfc3339;
+mod select;
 mod time_handler_guard;
 mod timeout;
 mod timer;
 mod timer_registry;
 
+pub use cron_schedule::{CronSchedule, InvalidCronSchedule};
+pub use event_log::TimerEvent;
+#[cfg(feature = "tracing-subscriber")]
+pub use format_time::MockFormatTime;
 pub use instant::Instant;
-pub use interval::Interval;
+pub use interval::{Interval, MissedTickBehavior};
 pub use time_handler_guard::TimeHandlerGuard;
 pub use timeout::{Elapsed, Timeout};
-pub use timer::TimerListener;
-pub use timer_registry::TimerRegistry;
+pub use timer::{PendingTimer, TimerKind, TimerListener};
+pub use timer_registry::{IdleSummary, NoTimerScheduled, TimerRegistry, TimerRegistryBuilder};
@@ -0,0 +1,179 @@
+use event_listener::{Event, EventListener};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Number of threads started by [`Watchdog::new`] that haven't finished yet, so tests can detect leaked threads.
+#[cfg(test)]
+pub(crate) static SLEEPING_THREADS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+#[cfg(test)]
+thread_local! {
+	/// Number of threads that have been started by [`Watchdog::new`] from the current thread,
+	/// so tests can detect if too many are started, even when other tests run concurrently.
+	pub(crate) static STARTED_THREADS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Finishes once the given amount of real time has passed.
+///
+/// Dropping the returned future wakes up the thread of its [`Watchdog`], so it doesn't outlive the sleep.
+pub(crate) fn sleep(duration: Duration) -> Sleep {
+	let watchdog = Watchdog::new();
+	let listener = watchdog.expire_after(duration);
+	Sleep {
+		listener,
+		_watchdog: watchdog,
+	}
+}
+
+/// Future returned by [`sleep`].
+pub(crate) struct Sleep {
+	listener: EventListener,
+	/// Only kept, so its thread stops once the sleep is dropped.
+	_watchdog: Watchdog,
+}
+
+impl Future for Sleep {
+	type Output = ();
+
+	fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+		Pin::new(&mut self.listener).poll(context)
+	}
+}
+
+/// Signals once a deadline in real time has passed, the deadline can be moved any number of times.
+///
+/// This is independent of any async runtime, which is why a single separate thread is used for sleeping.
+/// Dropping the watchdog wakes up that thread, so it doesn't outlive the watchdog.
+pub(crate) struct Watchdog {
+	shared: Arc<Shared>,
+}
+
+struct Shared {
+	state: Mutex<State>,
+	state_changed: Condvar,
+}
+
+#[derive(Default)]
+struct State {
+	/// Every deadline has its own event, so listeners for previous deadlines aren't notified.
+	deadline: Option<(Instant, Event)>,
+	is_dropped: bool,
+}
+
+impl Watchdog {
+	pub(crate) fn new() -> Self {
+		let shared = Arc::new(Shared {
+			state: Mutex::default(),
+			state_changed: Condvar::new(),
+		});
+		#[cfg(test)]
+		{
+			SLEEPING_THREADS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+			STARTED_THREADS.with(|started_threads| started_threads.set(started_threads.get() + 1));
+		}
+		thread::spawn({
+			let shared = shared.clone();
+			move || {
+				shared.watch();
+				#[cfg(test)]
+				SLEEPING_THREADS.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+			}
+		});
+		Self { shared }
+	}
+
+	/// Moves the deadline to `timeout` from now. The returned listener finishes once that deadline has passed,
+	/// listeners for previous deadlines don't finish anymore.
+	pub(crate) fn expire_after(&self, timeout: Duration) -> EventListener {
+		let expired = Event::new();
+		let listener = expired.listen();
+		self.shared.state.lock().expect("Mutex was poisoned").deadline = Some((Instant::now() + timeout, expired));
+
+		self.shared.state_changed.notify_one();
+		listener
+	}
+}
+
+impl Drop for Watchdog {
+	fn drop(&mut self) {
+		self.shared.state.lock().expect("Mutex was poisoned").is_dropped = true;
+		self.shared.state_changed.notify_one();
+	}
+}
+
+impl Shared {
+	fn watch(&self) {
+		let mut state = self.state.lock().expect("Mutex was poisoned");
+		while !state.is_dropped {
+			let remaining = state
+				.deadline
+				.as_ref()
+				.map(|(deadline, _)| deadline.saturating_duration_since(Instant::now()));
+			state = match remaining {
+				None => self.state_changed.wait(state).expect("Mutex was poisoned"),
+				Some(remaining) if !remaining.is_zero() => {
+					let (state, _) = self
+						.state_changed
+						.wait_timeout(state, remaining)
+						.expect("Mutex was poisoned");
+					state
+				}
+				Some(_) => {
+					if let Some((_, expired)) = state.deadline.take() {
+						expired.notify(usize::MAX);
+					}
+					state
+				}
+			};
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use std::sync::Weak;
+
+	#[tokio::test]
+	async fn should_sleep_for_at_least_the_given_duration() {
+		let start = Instant::now();
+
+		sleep(Duration::from_millis(10)).await;
+
+		assert!(start.elapsed() >= Duration::from_millis(10));
+	}
+
+	#[test]
+	fn dropping_should_stop_the_sleeping_thread() {
+		let sleep = sleep(Duration::from_secs(60 * 60));
+		let shared: Weak<_> = Arc::downgrade(&sleep._watchdog.shared);
+
+		drop(sleep);
+
+		// the thread releases its reference to the shared state once it has finished
+		let start = Instant::now();
+		while shared.upgrade().is_some() {
+			assert!(start.elapsed() < Duration::from_secs(10), "Thread is still sleeping");
+			thread::sleep(Duration::from_millis(1));
+		}
+	}
+
+	#[tokio::test]
+	async fn watchdog_should_only_expire_at_the_latest_deadline() {
+		let watchdog = Watchdog::new();
+		let previous_deadline = watchdog.expire_after(Duration::from_millis(10));
+		let start = Instant::now();
+
+		watchdog.expire_after(Duration::from_millis(50)).await;
+
+		assert!(start.elapsed() >= Duration::from_millis(50));
+		assert!(
+			futures_lite::future::poll_once(previous_deadline).await.is_none(),
+			"Listener of the previous deadline has finished"
+		);
+	}
+}
@@ -0,0 +1,48 @@
+use crate::calendar::{civil_from_days, unix_time, SECONDS_PER_DAY};
+use std::fmt::{Display, Formatter};
+use std::time::SystemTime;
+
+/// RFC 3339 representation of a [`SystemTime`] in UTC with microsecond precision.
+pub(crate) struct Rfc3339(pub(crate) SystemTime);
+
+impl Display for Rfc3339 {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+		let (seconds, nanoseconds) = unix_time(self.0);
+		let microseconds = nanoseconds / 1000;
+
+		let days = seconds.div_euclid(SECONDS_PER_DAY);
+		let seconds_of_day = seconds.rem_euclid(SECONDS_PER_DAY);
+		let (year, month, day) = civil_from_days(days);
+
+		write!(
+			formatter,
+			"{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{microseconds:06}Z",
+			seconds_of_day / 3600,
+			seconds_of_day / 60 % 60,
+			seconds_of_day % 60,
+		)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use std::time::{Duration, UNIX_EPOCH};
+
+	#[test]
+	fn should_format_the_unix_epoch() {
+		assert_eq!("1970-01-01T00:00:00.000000Z", Rfc3339(UNIX_EPOCH).to_string());
+	}
+
+	#[test]
+	fn should_format_times_after_the_unix_epoch() {
+		let time = UNIX_EPOCH + Duration::from_secs(1_709_251_199) + Duration::from_micros(123_456);
+		assert_eq!("2024-02-29T23:59:59.123456Z", Rfc3339(time).to_string());
+	}
+
+	#[test]
+	fn should_format_times_before_the_unix_epoch() {
+		let time = UNIX_EPOCH - Duration::from_secs(86_400) - Duration::from_micros(250_000);
+		assert_eq!("1969-12-30T23:59:59.750000Z", Rfc3339(time).to_string());
+	}
+}
@@ -0,0 +1,61 @@
+use pin_project_lite::pin_project;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Await two futures concurrently. Finishes once the first of them finishes.
+/// This is basically a simplified version of [`futures_util::future::select`].
+/// That could have been used, but it would introduce a dependency to [`futures_util`].
+pub(crate) fn select<LEFT, RIGHT>(left: LEFT, right: RIGHT) -> Select<LEFT, RIGHT> {
+	Select { left, right }
+}
+
+pub(crate) enum Either<LEFT, RIGHT> {
+	Left(LEFT),
+	Right(RIGHT),
+}
+
+pin_project! {
+	pub(crate) struct Select<LEFT, RIGHT> {
+		#[pin]
+		left: LEFT,
+		#[pin]
+		right: RIGHT,
+	}
+}
+
+impl<LEFT, RIGHT> Future for Select<LEFT, RIGHT>
+where
+	LEFT: Future,
+	RIGHT: Future,
+{
+	type Output = Either<LEFT::Output, RIGHT::Output>;
+
+	fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = self.project();
+
+		if let Poll::Ready(output) = this.left.poll(context) {
+			return Poll::Ready(Either::Left(output));
+		}
+
+		this.right.poll(context).map(Either::Right)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::select::{select, Either};
+	use std::future;
+
+	#[tokio::test]
+	async fn should_finish_with_left_if_both_are_ready() {
+		let output = select(future::ready(1), future::ready(2)).await;
+		assert!(matches!(output, Either::Left(1)));
+	}
+
+	#[tokio::test]
+	async fn should_finish_with_right_if_left_is_pending() {
+		let output = select(future::pending::<()>(), future::ready(2)).await;
+		assert!(matches!(output, Either::Right(2)));
+	}
+}
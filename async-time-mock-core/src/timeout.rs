@@ -1,4 +1,4 @@
-use crate::TimeHandlerGuard;
+use crate::{Instant, TimeHandlerGuard, TimerListener};
 use pin_project_lite::pin_project;
 use std::fmt::{Debug, Display, Formatter};
 use std::future::Future;
@@ -9,16 +9,30 @@ pin_project! {
 	pub struct Timeout<F> {
 		#[pin]
 		future: F,
-		sleep: Pin<Box<dyn Future<Output = TimeHandlerGuard> + Send>>,
+		#[pin]
+		sleep: TimerListener,
 	}
 }
 
 impl<F> Timeout<F> {
-	pub(crate) fn new(future: F, sleep: impl Future<Output = TimeHandlerGuard> + Send + 'static) -> Self {
-		let sleep = Box::pin(sleep);
+	pub(crate) fn new(future: F, sleep: TimerListener) -> Self {
 		Self { sleep, future }
 	}
 
+	/// Point in time the timeout expires at.
+	pub fn deadline(&self) -> Instant {
+		self.sleep.deadline()
+	}
+
+	/// Moves the point in time the timeout expires at to `deadline`, e.g. to extend it whenever there was progress.
+	/// Unlike creating a new timeout, this keeps the inner future.
+	///
+	/// # Panics
+	/// When `deadline` was created by a different instance of `TimerRegistry`.
+	pub fn reset(self: Pin<&mut Self>, deadline: Instant) {
+		self.project().sleep.reset(deadline);
+	}
+
 	pub fn get_ref(&self) -> &F {
 		&self.future
 	}
@@ -41,7 +55,7 @@ where
 	fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
 		let this = self.project();
 		use Poll::*;
-		if let Ready(guard) = this.sleep.as_mut().poll(context) {
+		if let Ready(guard) = this.sleep.poll(context) {
 			return Ready(Err(Elapsed(guard)));
 		};
 
@@ -1,35 +1,97 @@
 use crate::time_handler_guard::TimeHandlerFinished;
-use crate::TimeHandlerGuard;
+use crate::timer_registry::WeakTimerRegistry;
+use crate::{Instant, TimeHandlerGuard};
 use event_listener::{Event, EventListener};
 use pin_project_lite::pin_project;
+use std::fmt::{Debug, Display, Formatter};
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{ready, Context, Poll};
+use std::time::{Duration, SystemTime};
 
 pub(crate) struct Timer {
+	id: u64,
+	scheduled_at: Duration,
+	kind: TimerKind,
+	label: Option<Arc<str>>,
 	trigger: Event,
 	handler_finished_waiter: TimeHandlerFinished,
 }
 
 impl Timer {
-	pub(crate) fn new() -> (Self, TimerListener) {
+	pub(crate) fn new(
+		id: u64,
+		scheduled_at: Duration,
+		kind: TimerKind,
+		label: Option<Arc<str>>,
+		scheduled_in: ScheduledIn,
+		timer_registry: WeakTimerRegistry,
+	) -> (Self, TimerListener) {
+		let (timer, listener, handler_guard) = Self::armed(id, scheduled_at, kind, label.clone());
+		(
+			timer,
+			TimerListener {
+				listener,
+				handler_guard: Some(handler_guard),
+				id,
+				scheduled_at,
+				kind,
+				label,
+				scheduled_in,
+				timer_registry,
+			},
+		)
+	}
+
+	fn armed(
+		id: u64,
+		scheduled_at: Duration,
+		kind: TimerKind,
+		label: Option<Arc<str>>,
+	) -> (Self, EventListener, TimeHandlerGuard) {
 		let (handler_guard, handler_finished_waiter) = TimeHandlerGuard::new();
 		let trigger = Event::new();
 		let listener = trigger.listen();
 		(
 			Self {
+				id,
+				scheduled_at,
+				kind,
+				label,
 				trigger,
 				handler_finished_waiter,
 			},
-			TimerListener {
-				listener,
-				handler_guard: Some(handler_guard),
-			},
+			listener,
+			handler_guard,
 		)
 	}
 
+	pub(crate) fn id(&self) -> u64 {
+		self.id
+	}
+
+	/// Used for timers that are scheduled against the system time, once it is known
+	/// at which monotonic time they are actually triggered.
+	pub(crate) fn reschedule(&mut self, scheduled_at: Duration) {
+		self.scheduled_at = scheduled_at;
+	}
+
+	pub(crate) fn pending_timer(&self, timer_registry_id: u64) -> PendingTimer {
+		PendingTimer {
+			deadline: Instant::new(self.scheduled_at, timer_registry_id),
+			sequence: self.id,
+			kind: self.kind,
+			label: self.label.clone(),
+		}
+	}
+
 	pub(crate) fn trigger(self) -> TimeHandlerFinished {
 		let Self {
+			id: _,
+			scheduled_at: _,
+			kind: _,
+			label: _,
 			trigger,
 			handler_finished_waiter,
 		} = self;
@@ -38,12 +100,146 @@ impl Timer {
 	}
 }
 
+/// Snapshot of a timer that is scheduled in a [`TimerRegistry`](crate::TimerRegistry),
+/// see [`TimerRegistry::pending_timers`](crate::TimerRegistry::pending_timers).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingTimer {
+	/// Point in time the timer is scheduled to expire at.
+	pub deadline: Instant,
+	/// Timers are numbered in the order they were scheduled in, starting at 0.
+	pub sequence: u64,
+	/// Which kind of function the timer has been scheduled by.
+	pub kind: TimerKind,
+	/// Label the timer has been scheduled with, e.g. via [`TimerRegistry::sleep_labelled`](crate::TimerRegistry::sleep_labelled).
+	pub label: Option<Arc<str>>,
+}
+
+impl Display for PendingTimer {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+		let Self {
+			deadline,
+			sequence,
+			kind,
+			label,
+		} = self;
+		write!(formatter, "{kind:?} timer #{sequence}")?;
+		if let Some(label) = label {
+			write!(formatter, " ({label:?})")?;
+		}
+		write!(formatter, " scheduled at {:?}", deadline.as_duration())
+	}
+}
+
+/// What a timer has been scheduled for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TimerKind {
+	/// [`TimerRegistry::sleep`](crate::TimerRegistry::sleep) or [`TimerRegistry::sleep_until`](crate::TimerRegistry::sleep_until)
+	Sleep,
+	/// [`TimerRegistry::timeout`](crate::TimerRegistry::timeout) or [`TimerRegistry::timeout_at`](crate::TimerRegistry::timeout_at)
+	Timeout,
+	/// [`TimerRegistry::interval`](crate::TimerRegistry::interval) or [`TimerRegistry::interval_at`](crate::TimerRegistry::interval_at)
+	Interval,
+}
+
+/// Where a [`Timer`] is scheduled in its [`TimerRegistry`](crate::TimerRegistry), so it can be cancelled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ScheduledIn {
+	/// Scheduled by the time it expires at.
+	Time,
+	/// Scheduled by the system time it expires at.
+	SystemTime(SystemTime),
+}
+
 pin_project! {
-	#[derive(Debug)]
+	/// Future returned by [`TimerRegistry::sleep`](crate::TimerRegistry::sleep),
+	/// [`TimerRegistry::sleep_until`](crate::TimerRegistry::sleep_until) and
+	/// [`TimerRegistry::sleep_until_system_time`](crate::TimerRegistry::sleep_until_system_time).
+	///
+	/// Dropping it before the timer has expired cancels the timer and removes it from the [`TimerRegistry`](crate::TimerRegistry).
 	pub struct TimerListener {
 		#[pin]
 		listener: EventListener,
 		handler_guard: Option<TimeHandlerGuard>,
+		id: u64,
+		scheduled_at: Duration,
+		kind: TimerKind,
+		label: Option<Arc<str>>,
+		scheduled_in: ScheduledIn,
+		timer_registry: WeakTimerRegistry,
+	}
+
+	impl PinnedDrop for TimerListener {
+		fn drop(this: Pin<&mut Self>) {
+			let this = this.project();
+			this.timer_registry.cancel_timer(*this.scheduled_in, *this.scheduled_at, *this.id);
+		}
+	}
+}
+
+impl TimerListener {
+	/// Point in time the timer expires at.
+	///
+	/// For timers scheduled via [`TimerRegistry::sleep_until_system_time`](crate::TimerRegistry::sleep_until_system_time)
+	/// this is when the system time was expected to be reached at the time of scheduling,
+	/// later jumps of the system time aren't taken into account.
+	pub fn deadline(&self) -> Instant {
+		Instant::new(self.scheduled_at, self.timer_registry.id)
+	}
+
+	/// Whether the timer has expired, i.e. the time has been advanced to its [`deadline`](Self::deadline).
+	pub fn is_elapsed(&self) -> bool {
+		!self
+			.timer_registry
+			.is_scheduled(self.scheduled_in, self.scheduled_at, self.id)
+	}
+
+	/// Reschedules the timer to expire at `deadline` instead, even if it has already expired.
+	/// Unlike dropping the listener and scheduling a new timer, this keeps the position in the order
+	/// timers that expire at the same time are started in.
+	///
+	/// # Panics
+	/// When `deadline` was created by a different instance of `TimerRegistry`.
+	pub fn reset(self: Pin<&mut Self>, deadline: Instant) {
+		let this = self.project();
+		let at = deadline.into_duration(this.timer_registry.id);
+		let (id, kind) = (*this.id, *this.kind);
+		let (mut listener, handler_guard, label) = (this.listener, this.handler_guard, this.label);
+		this.timer_registry
+			.reschedule_timer(*this.scheduled_in, *this.scheduled_at, id, at, || {
+				// the timer has already been triggered, so it needs a new trigger and time handler
+				let (timer, new_listener, new_handler_guard) = Timer::armed(id, at, kind, label.clone());
+				listener.set(new_listener);
+				*handler_guard = Some(new_handler_guard);
+				timer
+			});
+		*this.scheduled_at = at;
+		*this.scheduled_in = ScheduledIn::Time;
+	}
+}
+
+impl Debug for TimerListener {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+		let Self {
+			listener,
+			handler_guard,
+			id,
+			scheduled_at,
+			kind,
+			label,
+			scheduled_in,
+			timer_registry,
+		} = self;
+		formatter
+			.debug_struct("TimerListener")
+			.field("listener", listener)
+			.field("handler_guard", handler_guard)
+			.field("id", id)
+			.field("scheduled_at", scheduled_at)
+			.field("kind", kind)
+			.field("label", label)
+			.field("scheduled_in", scheduled_in)
+			.field("timer_registry", timer_registry)
+			.finish_non_exhaustive()
 	}
 }
 
@@ -65,12 +261,20 @@ impl Future for TimerListener {
 #[cfg(test)]
 mod test {
 	use super::*;
+	use crate::TimerRegistry;
 	use futures_lite::future::poll_once;
 	use std::pin::pin;
 
 	#[tokio::test]
 	async fn timer_should_trigger_timer_listener() {
-		let (timer, listener) = Timer::new();
+		let (timer, listener) = Timer::new(
+			0,
+			Duration::ZERO,
+			TimerKind::Sleep,
+			None,
+			ScheduledIn::Time,
+			TimerRegistry::default().downgrade(),
+		);
 
 		let mut listener = pin!(listener);
 		assert!(
@@ -87,7 +291,14 @@ mod test {
 
 	#[tokio::test]
 	async fn time_handler_finished_should_be_triggered_by_time_handler_completion() {
-		let (timer, listener) = Timer::new();
+		let (timer, listener) = Timer::new(
+			0,
+			Duration::ZERO,
+			TimerKind::Sleep,
+			None,
+			ScheduledIn::Time,
+			TimerRegistry::default().downgrade(),
+		);
 
 		let time_handler_finished = timer.trigger();
 		let time_handler_guard = listener.await;
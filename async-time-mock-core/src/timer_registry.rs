@@ -1,54 +1,296 @@
 use crate::await_all::await_all;
+use crate::event_log::EventLog;
+use crate::real_time::{self, Watchdog};
+use crate::select::{select, Either};
 use crate::timeout::Timeout;
-use crate::timer::{Timer, TimerListener};
-use crate::{Instant, Interval};
+use crate::timer::{PendingTimer, ScheduledIn, Timer, TimerKind, TimerListener};
+use crate::{Instant, Interval, TimerEvent};
 use event_listener::Event;
 use std::collections::{BTreeMap, VecDeque};
-use std::fmt::{Debug, Formatter};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
 use std::future::Future;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, RwLock, RwLockWriteGuard};
+use std::pin::pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock, RwLockWriteGuard, Weak};
 use std::time::{Duration, SystemTime};
 
+/// Default number of consecutive yields without any change to the scheduled timers
+/// after which [`TimerRegistry::auto_advance`] considers the program idle.
+const DEFAULT_AUTO_ADVANCE_IDLE_YIELDS: usize = 16;
+
 pub struct TimerRegistry {
 	id: u64,
 	current_time: RwLock<Duration>,
-	timers_by_time: RwLock<TimersByTime>,
-	any_timer_scheduled_signal: Event,
+	timers_by_time: Arc<RwLock<TimersByTime>>,
+	timers_by_system_time: Arc<RwLock<TimersBySystemTime>>,
+	next_timer_id: AtomicU64,
+	any_timer_scheduled_signal: Arc<Event>,
 	advance_time_lock: async_lock::Mutex<()>,
+	time_handler_timeout: RwLock<Option<Duration>>,
+	auto_advance_idle_yields: AtomicUsize,
+	event_log: Arc<Mutex<EventLog>>,
+	start_time: Duration,
+	start_system_time: RwLock<SystemTime>,
 }
 
 impl Default for TimerRegistry {
+	fn default() -> Self {
+		TimerRegistryBuilder::default().build()
+	}
+}
+
+/// Builder for a [`TimerRegistry`] that doesn't start at the defaults, see [`TimerRegistry::builder`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TimerRegistryBuilder {
+	start_offset: Duration,
+	start_system_time: SystemTime,
+}
+
+impl Default for TimerRegistryBuilder {
 	fn default() -> Self {
 		Self {
-			id: Self::next_id(),
-			current_time: Default::default(),
+			start_offset: Duration::ZERO,
+			start_system_time: SystemTime::UNIX_EPOCH,
+		}
+	}
+}
+
+impl TimerRegistryBuilder {
+	/// Sets the [`TimerRegistry::system_time`] at the start. Defaults to [`SystemTime::UNIX_EPOCH`].
+	pub fn start_system_time(mut self, start_system_time: SystemTime) -> Self {
+		self.start_system_time = start_system_time;
+		self
+	}
+
+	/// Sets the offset of the monotonic test time at the start, so [`Instant`]s in the past of the start,
+	/// like `now - Duration::from_secs(10)`, can be represented. Defaults to [`Duration::ZERO`].
+	///
+	/// Without an offset, lookbacks like [`Instant::checked_sub`] return `None` at the start of a test, which real
+	/// instants practically never do. Code like rate limiters or sliding windows behaves more realistically with an
+	/// offset that is larger than the longest lookback, e.g. a day.
+	pub fn start_offset(mut self, start_offset: Duration) -> Self {
+		self.start_offset = start_offset;
+		self
+	}
+
+	pub fn build(self) -> TimerRegistry {
+		let Self {
+			start_offset,
+			start_system_time,
+		} = self;
+		TimerRegistry {
+			id: TimerRegistry::next_id(),
+			current_time: RwLock::new(start_offset),
 			timers_by_time: Default::default(),
+			timers_by_system_time: Default::default(),
+			next_timer_id: Default::default(),
 			any_timer_scheduled_signal: Default::default(),
 			advance_time_lock: Default::default(),
+			time_handler_timeout: Default::default(),
+			auto_advance_idle_yields: AtomicUsize::new(DEFAULT_AUTO_ADVANCE_IDLE_YIELDS),
+			event_log: Default::default(),
+			start_time: start_offset,
+			start_system_time: RwLock::new(start_system_time),
 		}
 	}
 }
 
-type TimersByTime = BTreeMap<Duration, VecDeque<Timer>>;
+/// Error returned by [`TimerRegistry::advance_time_with_timeout`] if no timer has been scheduled in time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NoTimerScheduled {
+	/// Real time that has been waited for a timer to be scheduled.
+	pub timeout: Duration,
+}
+
+impl Display for NoTimerScheduled {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(
+			formatter,
+			"No timer has been scheduled within {:?} of real time, time was not advanced.",
+			self.timeout
+		)
+	}
+}
+
+impl Error for NoTimerScheduled {}
+
+/// Result of [`TimerRegistry::advance_until_idle`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct IdleSummary {
+	/// Test time after advancing.
+	pub now: Instant,
+	/// Number of timers that have been started while advancing.
+	pub triggered_timers: usize,
+	/// `false` if there were still timers scheduled once the maximum time was reached.
+	pub is_idle: bool,
+}
+
+pub(crate) type TimersByTime = BTreeMap<Duration, VecDeque<Timer>>;
+pub(crate) type TimersBySystemTime = BTreeMap<SystemTime, VecDeque<Timer>>;
+
+/// The parts of a [`TimerRegistry`] a [`TimerListener`] needs to cancel or reschedule its timer,
+/// without keeping the [`TimerRegistry`] alive.
+#[derive(Clone, Debug)]
+pub(crate) struct WeakTimerRegistry {
+	pub(crate) id: u64,
+	timers_by_time: Weak<RwLock<TimersByTime>>,
+	timers_by_system_time: Weak<RwLock<TimersBySystemTime>>,
+	any_timer_scheduled_signal: Weak<Event>,
+	event_log: Weak<Mutex<EventLog>>,
+}
+
+impl WeakTimerRegistry {
+	/// Removes a timer that hasn't been triggered yet, e.g. because its [`TimerListener`] was dropped.
+	pub(crate) fn cancel_timer(&self, scheduled_in: ScheduledIn, scheduled_at: Duration, timer_id: u64) {
+		// Timers that have already been triggered aren't part of the registry anymore, so this is a no-op for them.
+		match scheduled_in {
+			ScheduledIn::Time => {
+				if let Some(timers_by_time) = self.timers_by_time.upgrade() {
+					let mut timers_by_time = timers_by_time.write().expect("RwLock was poisoned");
+					remove_timer(&mut timers_by_time, &scheduled_at, timer_id);
+				}
+			}
+			ScheduledIn::SystemTime(scheduled_at) => {
+				if let Some(timers_by_system_time) = self.timers_by_system_time.upgrade() {
+					let mut timers_by_system_time = timers_by_system_time.write().expect("RwLock was poisoned");
+					remove_timer(&mut timers_by_system_time, &scheduled_at, timer_id);
+				}
+			}
+		}
+	}
+
+	/// Whether a timer is still waiting to be triggered.
+	pub(crate) fn is_scheduled(&self, scheduled_in: ScheduledIn, scheduled_at: Duration, timer_id: u64) -> bool {
+		fn contains<Time: Ord>(timers: &BTreeMap<Time, VecDeque<Timer>>, scheduled_at: &Time, timer_id: u64) -> bool {
+			timers
+				.get(scheduled_at)
+				.is_some_and(|timers| timers.iter().any(|timer| timer.id() == timer_id))
+		}
+
+		match scheduled_in {
+			ScheduledIn::Time => self.timers_by_time.upgrade().is_some_and(|timers_by_time| {
+				contains(
+					&timers_by_time.read().expect("RwLock was poisoned"),
+					&scheduled_at,
+					timer_id,
+				)
+			}),
+			ScheduledIn::SystemTime(scheduled_at) => {
+				self.timers_by_system_time
+					.upgrade()
+					.is_some_and(|timers_by_system_time| {
+						contains(
+							&timers_by_system_time.read().expect("RwLock was poisoned"),
+							&scheduled_at,
+							timer_id,
+						)
+					})
+			}
+		}
+	}
+
+	/// Moves a timer to expire at `at` instead. If it has already been triggered, it is scheduled again
+	/// with the timer created by `rearm`.
+	pub(crate) fn reschedule_timer(
+		&self,
+		scheduled_in: ScheduledIn,
+		scheduled_at: Duration,
+		timer_id: u64,
+		at: Duration,
+		rearm: impl FnOnce() -> Timer,
+	) {
+		let (Some(timers_by_time), Some(timers_by_system_time)) =
+			(self.timers_by_time.upgrade(), self.timers_by_system_time.upgrade())
+		else {
+			// the registry is gone, so the time can't be advanced to trigger the timer anyways
+			return;
+		};
+
+		let scheduled = {
+			let mut timers_by_time = timers_by_time.write().expect("RwLock was poisoned");
+			let removed_timer = match scheduled_in {
+				ScheduledIn::Time => remove_timer(&mut timers_by_time, &scheduled_at, timer_id),
+				ScheduledIn::SystemTime(scheduled_at) => remove_timer(
+					&mut timers_by_system_time.write().expect("RwLock was poisoned"),
+					&scheduled_at,
+					timer_id,
+				),
+			};
+			let timer = match removed_timer {
+				Some(mut timer) => {
+					timer.reschedule(at);
+					timer
+				}
+				None => rearm(),
+			};
+			let scheduled = TimerEvent::Scheduled(timer.pending_timer(self.id));
+			insert_timer(&mut timers_by_time, at, timer);
+			scheduled
+		};
+		if let Some(event_log) = self.event_log.upgrade() {
+			record_event(&event_log, || scheduled);
+		}
+		if let Some(any_timer_scheduled_signal) = self.any_timer_scheduled_signal.upgrade() {
+			any_timer_scheduled_signal.notify(1);
+		}
+	}
+}
+
+/// Inserts the timer ordered by its id, so timers that expire at the same time are started in the order
+/// they were scheduled in, even if some of them have been rescheduled.
+fn insert_timer<Time: Ord>(timers: &mut BTreeMap<Time, VecDeque<Timer>>, at: Time, timer: Timer) {
+	let timers_at = timers.entry(at).or_default();
+	let index = timers_at.partition_point(|scheduled_timer| scheduled_timer.id() < timer.id());
+	timers_at.insert(index, timer);
+}
+
+fn remove_timer<Time: Ord>(
+	timers: &mut BTreeMap<Time, VecDeque<Timer>>,
+	scheduled_at: &Time,
+	timer_id: u64,
+) -> Option<Timer> {
+	let timers_at = timers.get_mut(scheduled_at)?;
+	let index = timers_at.iter().position(|timer| timer.id() == timer_id)?;
+	let timer = timers_at.remove(index);
+	if timers_at.is_empty() {
+		timers.remove(scheduled_at);
+	}
+	timer
+}
+
+/// Must not be called while holding any of the locks of the [`TimerRegistry`], because the `tracing` subscriber
+/// might need them, e.g. to format the mocked time.
+fn record_event(event_log: &Mutex<EventLog>, event: impl FnOnce() -> TimerEvent) {
+	#[cfg(feature = "tracing")]
+	let event = {
+		let event = event();
+		tracing::trace!(%event, "Timer event");
+		|| event
+	};
+
+	event_log.lock().expect("Mutex was poisoned").record(event);
+}
 
 impl TimerRegistry {
+	/// Creates a [`TimerRegistryBuilder`] to configure the start time of a new `TimerRegistry`.
+	pub fn builder() -> TimerRegistryBuilder {
+		TimerRegistryBuilder::default()
+	}
+
 	/// Schedules a timer to expire in "Duration", once expired, returns
 	/// a TimeHandlerGuard that must be dropped only once the timer event has been fully processed
 	/// (all sideeffects finished).
 	///
 	/// Roughly eqivalent to `async pub fn sleep(&self, duration: Duration) -> TimeHandlerGuard`.
 	pub fn sleep(&self, duration: Duration) -> TimerListener {
-		assert!(!duration.is_zero(), "Sleeping for zero time is not allowed");
-
-		let listener = {
-			let timers_by_time = self.timers_by_time.write().expect("RwLock was poisoned");
-			let wakeup_time = *self.current_time.read().expect("RwLock was poisoned") + duration;
-			Self::schedule_timer(timers_by_time, wakeup_time)
-		};
-		self.any_timer_scheduled_signal.notify(1);
+		self.schedule_in(duration, TimerKind::Sleep, None)
+	}
 
-		listener
+	/// Same as [`sleep`](Self::sleep), but attaches a label to the timer. The label is shown in diagnostics
+	/// like [`pending_timers`](Self::pending_timers), the `Debug` output and panic messages.
+	pub fn sleep_labelled(&self, duration: Duration, label: impl Into<Arc<str>>) -> TimerListener {
+		self.schedule_in(duration, TimerKind::Sleep, Some(label.into()))
 	}
 
 	/// Schedules a timer to expire at "Instant", once expired, returns
@@ -60,14 +302,15 @@ impl TimerRegistry {
 	/// # Panics
 	/// When `until` was created by a different instance of `TimerRegistry`.
 	pub fn sleep_until(&self, until: Instant) -> TimerListener {
-		let listener = {
-			let timers_by_time = self.timers_by_time.write().expect("RwLock was poisoned");
-			let wakeup_time = until.into_duration(self.id);
-			Self::schedule_timer(timers_by_time, wakeup_time)
-		};
-		self.any_timer_scheduled_signal.notify(1);
+		self.schedule_at(until, TimerKind::Sleep, None)
+	}
 
-		listener
+	/// Same as [`sleep_until`](Self::sleep_until), but attaches a label to the timer, see [`sleep_labelled`](Self::sleep_labelled).
+	///
+	/// # Panics
+	/// When `until` was created by a different instance of `TimerRegistry`.
+	pub fn sleep_until_labelled(&self, until: Instant, label: impl Into<Arc<str>>) -> TimerListener {
+		self.schedule_at(until, TimerKind::Sleep, Some(label.into()))
 	}
 
 	/// Combines a future with a `sleep` timer. If the future finishes before
@@ -80,7 +323,18 @@ impl TimerRegistry {
 	where
 		F: Future,
 	{
-		Timeout::new(future, self.sleep(timeout))
+		Timeout::new(future, self.schedule_in(timeout, TimerKind::Timeout, None))
+	}
+
+	/// Same as [`timeout`](Self::timeout), but attaches a label to the timer, see [`sleep_labelled`](Self::sleep_labelled).
+	pub fn timeout_labelled<F>(&self, timeout: Duration, future: F, label: impl Into<Arc<str>>) -> Timeout<F>
+	where
+		F: Future,
+	{
+		Timeout::new(
+			future,
+			self.schedule_in(timeout, TimerKind::Timeout, Some(label.into())),
+		)
 	}
 
 	/// Combines a future with a `sleep_until` timer. If the future finishes before
@@ -96,20 +350,124 @@ impl TimerRegistry {
 	where
 		F: Future,
 	{
-		Timeout::new(future, self.sleep_until(at))
+		Timeout::new(future, self.schedule_at(at, TimerKind::Timeout, None))
+	}
+
+	/// Same as [`timeout_at`](Self::timeout_at), but attaches a label to the timer, see [`sleep_labelled`](Self::sleep_labelled).
+	///
+	/// # Panics
+	/// When `at` was created by a different instance of `TimerRegistry`.
+	pub fn timeout_at_labelled<F>(&self, at: Instant, future: F, label: impl Into<Arc<str>>) -> Timeout<F>
+	where
+		F: Future,
+	{
+		Timeout::new(future, self.schedule_at(at, TimerKind::Timeout, Some(label.into())))
 	}
 
 	pub fn interval(self: &Arc<Self>, period: Duration) -> Interval {
-		Interval::new(self.clone(), self.now(), period)
+		Interval::new(self.clone(), self.now(), period, None)
+	}
+
+	/// Same as [`interval`](Self::interval), but attaches a label to every timer of the interval,
+	/// see [`sleep_labelled`](Self::sleep_labelled).
+	pub fn interval_labelled(self: &Arc<Self>, period: Duration, label: impl Into<Arc<str>>) -> Interval {
+		Interval::new(self.clone(), self.now(), period, Some(label.into()))
 	}
 
 	pub fn interval_at(self: &Arc<Self>, start: Instant, period: Duration) -> Interval {
-		Interval::new(self.clone(), start, period)
+		Interval::new(self.clone(), start, period, None)
+	}
+
+	/// Same as [`interval_at`](Self::interval_at), but attaches a label to every timer of the interval,
+	/// see [`sleep_labelled`](Self::sleep_labelled).
+	pub fn interval_at_labelled(
+		self: &Arc<Self>,
+		start: Instant,
+		period: Duration,
+		label: impl Into<Arc<str>>,
+	) -> Interval {
+		Interval::new(self.clone(), start, period, Some(label.into()))
+	}
+
+	fn schedule_in(&self, duration: Duration, kind: TimerKind, label: Option<Arc<str>>) -> TimerListener {
+		assert!(!duration.is_zero(), "Sleeping for zero time is not allowed");
+
+		let listener = {
+			let timers_by_time = self.timers_by_time.write().expect("RwLock was poisoned");
+			let wakeup_time = *self.current_time.read().expect("RwLock was poisoned") + duration;
+			self.schedule_timer(timers_by_time, wakeup_time, kind, label)
+		};
+		self.any_timer_scheduled_signal.notify(1);
+
+		listener
 	}
 
-	fn schedule_timer(mut timers_by_time: RwLockWriteGuard<'_, TimersByTime>, at: Duration) -> TimerListener {
-		let (timer, listener) = Timer::new();
-		timers_by_time.entry(at).or_default().push_back(timer);
+	pub(crate) fn schedule_at(&self, until: Instant, kind: TimerKind, label: Option<Arc<str>>) -> TimerListener {
+		let listener = {
+			let timers_by_time = self.timers_by_time.write().expect("RwLock was poisoned");
+			let wakeup_time = until.into_duration(self.id);
+			self.schedule_timer(timers_by_time, wakeup_time, kind, label)
+		};
+		self.any_timer_scheduled_signal.notify(1);
+
+		listener
+	}
+
+	fn schedule_timer(
+		&self,
+		mut timers_by_time: RwLockWriteGuard<'_, TimersByTime>,
+		at: Duration,
+		kind: TimerKind,
+		label: Option<Arc<str>>,
+	) -> TimerListener {
+		let id = self.next_timer_id.fetch_add(1, Ordering::Relaxed);
+		let (timer, listener) = Timer::new(id, at, kind, label, ScheduledIn::Time, self.downgrade());
+		let scheduled = TimerEvent::Scheduled(timer.pending_timer(self.id));
+		insert_timer(&mut timers_by_time, at, timer);
+		drop(timers_by_time);
+
+		self.record_event(|| scheduled);
+		listener
+	}
+
+	/// Schedules a timer to expire once the [`system_time`](Self::system_time) reaches `until`, once expired, returns
+	/// a TimeHandlerGuard that must be dropped only once the timer event has been fully processed
+	/// (all sideeffects finished).
+	///
+	/// Unlike [`sleep_until`](Self::sleep_until), this follows jumps of the system time, see
+	/// [`set_system_time`](Self::set_system_time). If the system time jumps to or past `until`,
+	/// the timer expires the next time the time is advanced.
+	pub fn sleep_until_system_time(&self, until: SystemTime) -> TimerListener {
+		self.schedule_at_system_time(until, None)
+	}
+
+	/// Same as [`sleep_until_system_time`](Self::sleep_until_system_time), but attaches a label to the timer,
+	/// see [`sleep_labelled`](Self::sleep_labelled).
+	pub fn sleep_until_system_time_labelled(&self, until: SystemTime, label: impl Into<Arc<str>>) -> TimerListener {
+		self.schedule_at_system_time(until, Some(label.into()))
+	}
+
+	fn schedule_at_system_time(&self, until: SystemTime, label: Option<Arc<str>>) -> TimerListener {
+		let (listener, scheduled) = {
+			let mut timers_by_system_time = self.timers_by_system_time.write().expect("RwLock was poisoned");
+			let id = self.next_timer_id.fetch_add(1, Ordering::Relaxed);
+			let start_system_time = *self.start_system_time.read().expect("RwLock was poisoned");
+			let scheduled_at = self.time_at_system_time(until, start_system_time, self.current_time());
+			let (timer, listener) = Timer::new(
+				id,
+				scheduled_at,
+				TimerKind::Sleep,
+				label,
+				ScheduledIn::SystemTime(until),
+				self.downgrade(),
+			);
+			let scheduled = TimerEvent::Scheduled(timer.pending_timer(self.id));
+			insert_timer(&mut timers_by_system_time, until, timer);
+			(listener, scheduled)
+		};
+		self.record_event(|| scheduled);
+		self.any_timer_scheduled_signal.notify(1);
+
 		listener
 	}
 
@@ -118,48 +476,488 @@ impl TimerRegistry {
 	/// 1. By time they are scheduled to run at
 	/// 2. By the order they were scheduled
 	///
-	/// If no timer has been scheduled yet, waits until one is.
+	/// If no timer is currently scheduled, waits until one is.
 	/// Returns only once all started timers have finished processing.
+	///
+	/// # Panics
+	/// When the timeout set via [`set_time_handler_timeout`](Self::set_time_handler_timeout) is exceeded.
 	pub async fn advance_time(&self, by_duration: Duration) {
 		let _guard = self.advance_time_lock.lock().await;
 
-		let finished_time = *self.current_time.read().expect("RwLock was poisoned") + by_duration;
+		self.wait_for_first_timer().await;
+		self.advance_time_until(self.current_time() + by_duration).await
+	}
+
+	/// Advances test time to the given `Instant`. Starts all scheduled timers that have expired
+	/// up to and including `until`, in the same order as [`advance_time`](Self::advance_time).
+	///
+	/// If `until` lies in the past, the time is not changed, but timers that are already due are still started.
+	///
+	/// If no timer is currently scheduled, waits until one is.
+	/// Returns only once all started timers have finished processing.
+	///
+	/// # Panics
+	/// * When `until` was created by a different instance of `TimerRegistry`.
+	/// * When the timeout set via [`set_time_handler_timeout`](Self::set_time_handler_timeout) is exceeded.
+	pub async fn advance_to(&self, until: Instant) {
+		let until = until.into_duration(self.id);
+		let _guard = self.advance_time_lock.lock().await;
+
+		self.wait_for_first_timer().await;
+		self.advance_time_until(until.max(self.current_time())).await
+	}
+
+	/// Same as [`advance_time`](Self::advance_time), but doesn't wait for a timer to be scheduled.
+	/// If no timer is scheduled, the time is advanced immediately.
+	pub async fn advance_time_without_waiting(&self, by_duration: Duration) {
+		let _guard = self.advance_time_lock.lock().await;
+
+		self.advance_time_until(self.current_time() + by_duration).await
+	}
+
+	/// Same as [`advance_time`](Self::advance_time), but waits at most `timeout` of real time for the first timer
+	/// to be scheduled. Use this to detect code that never schedules a timer instead of waiting forever.
+	///
+	/// Returns [`NoTimerScheduled`] without advancing the time if no timer has been scheduled in time.
+	pub async fn advance_time_with_timeout(
+		&self,
+		by_duration: Duration,
+		timeout: Duration,
+	) -> Result<(), NoTimerScheduled> {
+		let _guard = self.advance_time_lock.lock().await;
+
+		// start listening before checking to not miss a timer that is scheduled in between
+		let timer_scheduled = self.any_timer_scheduled_signal.listen();
+		if self.has_no_timers() {
+			// the real time sleep is only started when waiting, it is dropped right after the select, stopping its thread
+			if let Either::Right(()) = select(timer_scheduled, real_time::sleep(timeout)).await {
+				return Err(NoTimerScheduled { timeout });
+			}
+		}
+
+		self.advance_time_until(self.current_time() + by_duration).await;
+		Ok(())
+	}
 
-		if self.timers_by_time.read().expect("RwLock was poisoned").is_empty() {
-			// If no timer has been scheduled yet, wait for one to be scheduled
-			self.any_timer_scheduled_signal.listen().await;
+	/// If no timer is scheduled, waits for one to be scheduled.
+	async fn wait_for_first_timer(&self) {
+		// start listening before checking to not miss a timer that is scheduled in between
+		let timer_scheduled = self.any_timer_scheduled_signal.listen();
+		if self.has_no_timers() {
+			timer_scheduled.await;
 		}
+	}
 
+	fn has_no_timers(&self) -> bool {
+		self.timers_by_time.read().expect("RwLock was poisoned").is_empty()
+			&& self
+				.timers_by_system_time
+				.read()
+				.expect("RwLock was poisoned")
+				.is_empty()
+	}
+
+	/// Expects the `advance_time_lock` to be held.
+	async fn advance_time_until(&self, finished_time: Duration) {
+		let run_all_timers = async {
+			let mut watchdog = None;
+			while let Some(timers_to_run) = self.take_next_timers(finished_time) {
+				self.run_timers(timers_to_run, &mut watchdog).await;
+			}
+		};
+		#[cfg(feature = "tracing")]
+		let run_all_timers = tracing::Instrument::instrument(
+			run_all_timers,
+			tracing::debug_span!("advance_time", from = ?self.current_time(), to = ?finished_time),
+		);
+		run_all_timers.await;
+
+		let time_advanced = self.move_current_time_to(
+			&mut self.current_time.write().expect("RwLock was poisoned"),
+			finished_time,
+		);
+		self.record_events(time_advanced);
+	}
+
+	/// Advances test time to the point in time the next timer is scheduled for and starts all timers
+	/// that are scheduled for this point in time in the order they were scheduled.
+	///
+	/// Unlike [`advance_time`](Self::advance_time), this doesn't wait for a timer to be scheduled.
+	/// Returns the `Instant` the time was advanced to once all started timers have finished processing
+	/// or `None` if no timer was scheduled.
+	pub async fn advance_to_next_timer(&self) -> Option<Instant> {
+		let _guard = self.advance_time_lock.lock().await;
+
+		let timers_to_run = self.take_next_timers(Duration::MAX)?;
+		let now = self.now();
+		self.run_timers(timers_to_run, &mut None).await;
+
+		Some(now)
+	}
+
+	/// Sets the maximum amount of real time to wait for the [`TimeHandlerGuard`](crate::TimeHandlerGuard)s
+	/// of started timers to be dropped while advancing the time. Without a timeout (the default),
+	/// advancing the time waits forever.
+	///
+	/// Use this to detect time handlers that never drop their guard instead of hanging.
+	/// Once the timeout is exceeded, advancing the time panics with a list of the timers whose guards are still held.
+	pub fn set_time_handler_timeout(&self, timeout: Option<Duration>) {
+		*self.time_handler_timeout.write().expect("RwLock was poisoned") = timeout;
+	}
+
+	/// Sets the number of consecutive yields without any change to the scheduled timers after which
+	/// [`auto_advance`](Self::auto_advance) considers the program idle and advances the time. Defaults to 16.
+	///
+	/// Increase this if tasks that don't wait for the test time are overtaken by [`auto_advance`](Self::auto_advance).
+	pub fn set_auto_advance_idle_yields(&self, idle_yields: usize) {
+		self.auto_advance_idle_yields.store(idle_yields, Ordering::Relaxed);
+	}
+
+	/// Repeatedly advances test time to the next scheduled timer, see [`advance_to_next_timer`](Self::advance_to_next_timer),
+	/// until no timers are left. This includes timers that are scheduled while processing other timers, as long as
+	/// they are scheduled before the [`TimeHandlerGuard`](crate::TimeHandlerGuard) of the timer being processed is dropped.
+	///
+	/// If `max` is given, time isn't advanced further than `max` from the current time. Use this to guard against
+	/// timers that are rescheduled forever, like an [`Interval`]. If timers are still scheduled after that,
+	/// the time is advanced by exactly `max`.
+	///
+	/// Unlike [`advance_time`](Self::advance_time), this doesn't wait for a timer to be scheduled.
+	pub async fn advance_until_idle(&self, max: Option<Duration>) -> IdleSummary {
+		let _guard = self.advance_time_lock.lock().await;
+
+		let finished_time = max.map(|max| self.current_time() + max);
+
+		let mut triggered_timers = 0;
+		let mut watchdog = None;
+		while let Some(timers_to_run) = self.take_next_timers(finished_time.unwrap_or(Duration::MAX)) {
+			triggered_timers += timers_to_run.len();
+			self.run_timers(timers_to_run, &mut watchdog).await;
+		}
+
+		let is_idle = self.has_no_timers();
+		if let (false, Some(finished_time)) = (is_idle, finished_time) {
+			let time_advanced = self.move_current_time_to(
+				&mut self.current_time.write().expect("RwLock was poisoned"),
+				finished_time,
+			);
+			self.record_events(time_advanced);
+		}
+
+		IdleSummary {
+			now: self.now(),
+			triggered_timers,
+			is_idle,
+		}
+	}
+
+	/// Drives the test time automatically: Whenever the rest of the program looks idle, the time is advanced
+	/// to the next scheduled timer, see [`advance_to_next_timer`](Self::advance_to_next_timer). If no timer is
+	/// scheduled, it waits until there is one. This never finishes, so run it in a separate task and drop it once done.
+	///
+	/// `yield_now` needs to yield to the async runtime, e.g. `tokio::task::yield_now`, so other tasks can make
+	/// progress.
+	///
+	/// Whether the program is idle is only guessed: It is considered idle once the scheduled timers haven't changed
+	/// for a number of consecutive yields, see [`set_auto_advance_idle_yields`](Self::set_auto_advance_idle_yields).
+	/// Progress of other tasks isn't detected, so tasks that take longer than that without scheduling or cancelling
+	/// timers, e.g. because they yield a lot or wait for real I/O, are overtaken by the advancing time. This can make
+	/// a timeout around such a task elapse, even though the task never waits for the test time.
+	pub async fn auto_advance<YieldNow, Yielded>(&self, mut yield_now: YieldNow) -> !
+	where
+		YieldNow: FnMut() -> Yielded,
+		Yielded: Future<Output = ()>,
+	{
 		loop {
-			let timers_to_run = {
-				let mut timers_by_time = self.timers_by_time.write().expect("RwLock was poisoned");
-				match timers_by_time.keys().next() {
-					Some(&key) if key <= finished_time => {
-						let mut current_time = self.current_time.write().expect("RwLock was poisoned");
-						*current_time = key.max(*current_time);
-						timers_by_time
-							.remove(&key)
-							.unwrap_or_else(|| unreachable!("We just checked that it exists"))
-					}
-					_ => break,
+			let mut pending_timers = self.pending_timers();
+			let mut idle_yields = 0;
+			while idle_yields < self.auto_advance_idle_yields.load(Ordering::Relaxed) {
+				yield_now().await;
+
+				let current_pending_timers = self.pending_timers();
+				if current_pending_timers == pending_timers {
+					idle_yields += 1;
+				} else {
+					pending_timers = current_pending_timers;
+					idle_yields = 0;
 				}
-			};
+			}
+
+			if pending_timers.is_empty() {
+				self.wait_for_first_timer().await;
+			} else {
+				self.advance_to_next_timer().await;
+			}
+		}
+	}
+
+	/// Removes the earliest timers if they are scheduled at or before `finished_time`
+	/// and moves the current time forward to when they are scheduled.
+	fn take_next_timers(&self, finished_time: Duration) -> Option<VecDeque<Timer>> {
+		let mut timers_by_time = self.timers_by_time.write().expect("RwLock was poisoned");
+		let mut timers_by_system_time = self.timers_by_system_time.write().expect("RwLock was poisoned");
+		let start_system_time = *self.start_system_time.read().expect("RwLock was poisoned");
+		let mut current_time = self.current_time.write().expect("RwLock was poisoned");
+
+		let next_time = timers_by_time.keys().next().copied();
+		let next_system_time = timers_by_system_time
+			.keys()
+			.next()
+			.map(|&system_time| self.time_at_system_time(system_time, start_system_time, *current_time));
+		let key = match (next_time, next_system_time) {
+			(Some(time), Some(system_time)) => time.min(system_time),
+			(time, system_time) => time.or(system_time)?,
+		};
+		if key > finished_time {
+			return None;
+		}
 
-			await_all(timers_to_run.into_iter().map(|timer| timer.trigger().wait())).await;
+		let time_advanced = self.move_current_time_to(&mut current_time, key);
+		let mut timers = VecDeque::new();
+		if next_time == Some(key) {
+			timers.extend(timers_by_time.remove(&key).into_iter().flatten());
+		}
+		if next_system_time == Some(key) {
+			let (_, system_timers) = timers_by_system_time.pop_first()?;
+			timers.extend(system_timers.into_iter().map(|mut timer| {
+				timer.reschedule(key);
+				timer
+			}));
+			// both buckets are ordered by id already, but need to be merged
+			timers.make_contiguous().sort_by_key(Timer::id);
 		}
+		drop((timers_by_time, timers_by_system_time, current_time));
+
+		self.record_events(time_advanced);
+		Some(timers)
+	}
 
-		*self.current_time.write().expect("RwLock was poisoned") = finished_time;
+	/// Test time at which the system time reaches `system_time`, but not before `current_time`.
+	fn time_at_system_time(
+		&self,
+		system_time: SystemTime,
+		start_system_time: SystemTime,
+		current_time: Duration,
+	) -> Duration {
+		let since_start = system_time.duration_since(start_system_time).unwrap_or_default();
+		(self.start_time + since_start).max(current_time)
 	}
 
-	/// Current test time, increases on every call to [`advance_time`].
+	/// Never moves the current time backwards.
+	/// Returns the event to record once the lock of the current time has been released.
+	#[must_use]
+	fn move_current_time_to(&self, current_time: &mut Duration, to: Duration) -> Option<TimerEvent> {
+		if to <= *current_time {
+			return None;
+		}
+
+		let time_advanced = TimerEvent::TimeAdvanced {
+			from: Instant::new(*current_time, self.id),
+			to: Instant::new(to, self.id),
+		};
+		*current_time = to;
+		Some(time_advanced)
+	}
+
+	/// The `watchdog` for the [time handler timeout](Self::set_time_handler_timeout) is only started when needed
+	/// and then reused, so running many batches of timers doesn't start a thread for each of them.
+	async fn run_timers(&self, timers: VecDeque<Timer>, watchdog: &mut Option<Watchdog>) {
+		#[cfg(feature = "tracing")]
+		let span = tracing::debug_span!("run_timers", mock_time = ?self.current_time(), timer_count = timers.len());
+		#[cfg(feature = "tracing")]
+		let waiting_since = std::time::Instant::now();
+
+		let run_timers = self.trigger_timers_and_wait(timers, watchdog);
+		#[cfg(feature = "tracing")]
+		let run_timers = tracing::Instrument::instrument(run_timers, span.clone());
+		run_timers.await;
+
+		#[cfg(feature = "tracing")]
+		span.in_scope(|| tracing::debug!(waited_for = ?waiting_since.elapsed(), "All time handlers finished"));
+	}
+
+	async fn trigger_timers_and_wait(&self, timers: VecDeque<Timer>, watchdog: &mut Option<Watchdog>) {
+		let pending_timers = timers
+			.iter()
+			.map(|timer| timer.pending_timer(self.id))
+			.collect::<Vec<_>>();
+		let mut handlers_finished = pin!(await_all(timers.into_iter().zip(pending_timers.clone()).map(
+			|(timer, pending_timer)| {
+				self.record_event(|| TimerEvent::Triggered(pending_timer.clone()));
+				let handler_finished = timer.trigger();
+				async move {
+					handler_finished.wait().await;
+					self.record_event(|| TimerEvent::HandlerFinished(pending_timer));
+				}
+			}
+		)));
+
+		let Some(timeout) = *self.time_handler_timeout.read().expect("RwLock was poisoned") else {
+			return handlers_finished.await;
+		};
+
+		let watchdog = watchdog.get_or_insert_with(Watchdog::new);
+		if let Either::Right(()) = select(handlers_finished.as_mut(), watchdog.expire_after(timeout)).await {
+			let unfinished_timers = pending_timers
+				.iter()
+				.enumerate()
+				.filter(|(index, _)| !handlers_finished.is_finished(*index))
+				.map(|(_, pending_timer)| format!("\n* {pending_timer}"))
+				.collect::<String>();
+			panic!(
+				"TimeHandlerGuards haven't been dropped within {timeout:?} for the following timers:{unfinished_timers}"
+			);
+		}
+	}
+
+	/// Starts recording what happens in this `TimerRegistry`, keeping the latest `capacity` events.
+	/// Passing `None` (the default) stops recording and discards the recorded events.
+	///
+	/// Use [`event_log`](Self::event_log) to get the recorded events, e.g. to print a timeline once a test failed
+	/// or to compare the timelines of multiple runs to detect nondeterminism.
+	pub fn set_event_log_capacity(&self, capacity: Option<usize>) {
+		self.event_log
+			.lock()
+			.expect("Mutex was poisoned")
+			.set_capacity(capacity);
+	}
+
+	/// Events that have been recorded since enabling the event log via [`set_event_log_capacity`](Self::set_event_log_capacity),
+	/// oldest first. Use the `Display` implementation of [`TimerEvent`] for a human readable timeline that is comparable
+	/// between different `TimerRegistry` instances.
+	pub fn event_log(&self) -> Vec<TimerEvent> {
+		self.event_log.lock().expect("Mutex was poisoned").events()
+	}
+
+	/// Must not be called while holding any of the locks of the `TimerRegistry`, see [`record_event`].
+	fn record_event(&self, event: impl FnOnce() -> TimerEvent) {
+		record_event(&self.event_log, event);
+	}
+
+	fn record_events(&self, events: impl IntoIterator<Item = TimerEvent>) {
+		for event in events {
+			self.record_event(|| event);
+		}
+	}
+
+	pub(crate) fn downgrade(&self) -> WeakTimerRegistry {
+		WeakTimerRegistry {
+			id: self.id,
+			timers_by_time: Arc::downgrade(&self.timers_by_time),
+			timers_by_system_time: Arc::downgrade(&self.timers_by_system_time),
+			any_timer_scheduled_signal: Arc::downgrade(&self.any_timer_scheduled_signal),
+			event_log: Arc::downgrade(&self.event_log),
+		}
+	}
+
+	/// Snapshot of all timers that are currently scheduled, ordered by the time they are scheduled to expire at
+	/// and by the order they were scheduled in.
+	pub fn pending_timers(&self) -> Vec<PendingTimer> {
+		let mut pending_timers = self
+			.timers_by_time
+			.read()
+			.expect("RwLock was poisoned")
+			.values()
+			.flatten()
+			.map(|timer| timer.pending_timer(self.id))
+			.collect::<Vec<_>>();
+
+		let timers_by_system_time = self.timers_by_system_time.read().expect("RwLock was poisoned");
+		if timers_by_system_time.is_empty() {
+			return pending_timers;
+		}
+
+		let start_system_time = *self.start_system_time.read().expect("RwLock was poisoned");
+		let current_time = self.current_time();
+		for (&system_time, timers) in timers_by_system_time.iter() {
+			let deadline = Instant::new(
+				self.time_at_system_time(system_time, start_system_time, current_time),
+				self.id,
+			);
+			pending_timers.extend(timers.iter().map(|timer| PendingTimer {
+				deadline,
+				..timer.pending_timer(self.id)
+			}));
+		}
+		pending_timers.sort_by_key(|pending_timer| (pending_timer.deadline, pending_timer.sequence));
+		pending_timers
+	}
+
+	/// Number of timers that are currently scheduled.
+	pub fn pending_timer_count(&self) -> usize {
+		let timers_by_time = self.timers_by_time.read().expect("RwLock was poisoned");
+		let timers_by_system_time = self.timers_by_system_time.read().expect("RwLock was poisoned");
+		timers_by_time
+			.values()
+			.chain(timers_by_system_time.values())
+			.map(VecDeque::len)
+			.sum()
+	}
+
+	/// Point in time the next timer is scheduled to expire at or `None` if no timer is scheduled.
+	pub fn next_deadline(&self) -> Option<Instant> {
+		self.pending_timers()
+			.first()
+			.map(|pending_timer| pending_timer.deadline)
+	}
+
+	fn current_time(&self) -> Duration {
+		*self.current_time.read().expect("RwLock was poisoned")
+	}
+
+	/// Current test time, increases on every call to [`advance_time`] or [`advance_to`].
 	pub fn now(&self) -> Instant {
 		Instant::new(*self.current_time.read().expect("RwLock was poisoned"), self.id)
 	}
 
 	/// Current test time. Similar to [`now`] but simulating system time, not monotonic time.
-	/// Increases on every call to [`advance_time`].
+	/// Increases on every call to [`advance_time`]. Starts at [`TimerRegistryBuilder::start_system_time`].
 	pub fn system_time(&self) -> SystemTime {
-		SystemTime::UNIX_EPOCH + *self.current_time.read().expect("RwLock was poisoned")
+		let elapsed = self.current_time() - self.start_time;
+		*self.start_system_time.read().expect("RwLock was poisoned") + elapsed
+	}
+
+	/// Lets the [`system_time`](Self::system_time) jump forward by `by`, e.g. to simulate an NTP correction.
+	/// The monotonic test time isn't advanced, so no timers are triggered.
+	pub fn jump_system_time_forward(&self, by: Duration) {
+		self.move_system_time(|system_time| system_time + by);
+	}
+
+	/// Lets the [`system_time`](Self::system_time) jump backward by `by`, e.g. to simulate an NTP correction.
+	/// The monotonic test time isn't moved, so no timers are affected.
+	///
+	/// # Panics
+	/// If the start system time moved back by `by` can't be represented by [`SystemTime`].
+	pub fn jump_system_time_backward(&self, by: Duration) {
+		self.move_system_time(|system_time| system_time - by);
+	}
+
+	/// Lets the [`system_time`](Self::system_time) jump to `system_time`, either forward or backward,
+	/// e.g. to simulate a manual change of the clock. The monotonic test time isn't moved, so no timers are affected.
+	///
+	/// # Panics
+	/// If `system_time` is so early that the start system time can't be represented by [`SystemTime`].
+	pub fn set_system_time(&self, system_time: SystemTime) {
+		let from = {
+			let mut start_system_time = self.start_system_time.write().expect("RwLock was poisoned");
+			let elapsed = self.current_time() - self.start_time;
+			let from = *start_system_time + elapsed;
+			*start_system_time = system_time - elapsed;
+			from
+		};
+		self.record_event(|| TimerEvent::SystemTimeJumped { from, to: system_time });
+	}
+
+	/// Moves the start system time while holding its lock, so the jump is relative to the system time
+	/// at that moment, even if the time is advanced concurrently.
+	fn move_system_time(&self, move_system_time: impl FnOnce(SystemTime) -> SystemTime) {
+		let (from, to) = {
+			let mut start_system_time = self.start_system_time.write().expect("RwLock was poisoned");
+			let elapsed = self.current_time() - self.start_time;
+			let from = *start_system_time + elapsed;
+			*start_system_time = move_system_time(*start_system_time);
+			(from, *start_system_time + elapsed)
+		};
+		self.record_event(|| TimerEvent::SystemTimeJumped { from, to });
 	}
 
 	fn next_id() -> u64 {
@@ -175,13 +973,79 @@ impl Debug for TimerRegistry {
 			id,
 			current_time,
 			timers_by_time: _,
+			timers_by_system_time: _,
+			next_timer_id: _,
 			any_timer_scheduled_signal: _,
 			advance_time_lock: _,
+			time_handler_timeout,
+			auto_advance_idle_yields,
+			event_log: _,
+			start_time: _,
+			start_system_time: _,
 		} = self;
 		formatter
 			.debug_struct("TimerRegistry")
 			.field("id", id)
 			.field("current_time", current_time)
+			.field("system_time", &self.system_time())
+			.field("time_handler_timeout", time_handler_timeout)
+			.field("auto_advance_idle_yields", auto_advance_idle_yields)
+			.field("pending_timers", &self.pending_timers())
 			.finish_non_exhaustive()
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use std::cell::Cell;
+
+	#[tokio::test]
+	async fn time_handler_timeout_should_not_leave_sleeping_threads_behind() {
+		let timer_registry = TimerRegistry::default();
+		timer_registry.set_time_handler_timeout(Some(Duration::from_secs(30)));
+		let sleeps = (1..=100)
+			.map(|seconds| timer_registry.sleep(Duration::from_secs(seconds)))
+			.collect::<Vec<_>>();
+
+		let (summary, ()) = tokio::join!(timer_registry.advance_until_idle(None), async {
+			for sleep in sleeps {
+				drop(sleep.await);
+			}
+		});
+		assert_eq!(100, summary.triggered_timers);
+		assert_eq!(
+			1,
+			real_time::STARTED_THREADS.with(Cell::get),
+			"Started more than one watchdog thread"
+		);
+
+		// the watchdog thread is woken up once advancing the time has finished, so it should stop quickly
+		let start = std::time::Instant::now();
+		while real_time::SLEEPING_THREADS.load(Ordering::SeqCst) > 0 {
+			assert!(
+				start.elapsed() < Duration::from_secs(10),
+				"Watchdog threads are still sleeping"
+			);
+			std::thread::sleep(Duration::from_millis(1));
+		}
+	}
+
+	#[tokio::test]
+	async fn advance_time_with_timeout_should_not_start_a_thread_if_a_timer_is_scheduled() {
+		let timer_registry = TimerRegistry::default();
+		let sleep = timer_registry.sleep(Duration::from_secs(1));
+
+		let (result, ()) = tokio::join!(
+			timer_registry.advance_time_with_timeout(Duration::from_secs(1), Duration::from_secs(60)),
+			async { drop(sleep.await) },
+		);
+		result.expect("Timer was already scheduled");
+
+		assert_eq!(
+			0,
+			real_time::STARTED_THREADS.with(Cell::get),
+			"Started a real time sleep"
+		);
+	}
+}
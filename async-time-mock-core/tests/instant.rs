@@ -1,4 +1,5 @@
 use async_time_mock_core::{Instant, TimerRegistry};
+use std::time::Duration;
 
 #[test]
 #[should_panic]
@@ -28,6 +29,27 @@ fn should_not_allow_subtracting_two_instants_from_different_timer_registries() {
 	let _ = instant2 - instant1;
 }
 
+#[test]
+fn checked_sub_should_look_back_before_the_start_with_a_start_offset() {
+	let timer_registry = TimerRegistry::builder()
+		.start_offset(Duration::from_secs(24 * 60 * 60))
+		.build();
+	let now = timer_registry.now();
+
+	let a_minute_ago = now
+		.checked_sub(Duration::from_secs(60))
+		.expect("Lookback should be possible");
+	assert_eq!(Duration::from_secs(60), now.duration_since(a_minute_ago));
+	assert_eq!(Duration::from_secs(60), a_minute_ago.elapsed(&timer_registry));
+}
+
+#[test]
+fn checked_sub_should_not_look_back_before_the_start_without_a_start_offset() {
+	let timer_registry = TimerRegistry::default();
+
+	assert_eq!(None, timer_registry.now().checked_sub(Duration::from_secs(60)));
+}
+
 fn instants_from_different_timer_registries() -> (Instant, Instant) {
 	let timer_registry1 = TimerRegistry::default();
 	let timer_registry2 = TimerRegistry::default();
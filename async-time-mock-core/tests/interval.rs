@@ -1,4 +1,4 @@
-use async_time_mock_core::TimerRegistry;
+use async_time_mock_core::{Instant, Interval, MissedTickBehavior, TimerRegistry};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -129,3 +129,113 @@ async fn should_schedule_periodic_timer_at() {
 
 	join_handle.await.expect("Interval task crashed");
 }
+
+#[tokio::test]
+async fn should_burst_missed_ticks_by_default() {
+	let (start, ticks, missed_ticks) = three_ticks_starting_in_the_past(MissedTickBehavior::Burst).await;
+
+	assert_eq!(
+		vec![
+			start - Duration::from_millis(3500),
+			start - Duration::from_millis(2500),
+			start - Duration::from_millis(1500),
+		],
+		ticks
+	);
+	assert_eq!(3, missed_ticks);
+}
+
+#[tokio::test]
+async fn should_delay_ticks_after_a_missed_tick() {
+	let (start, ticks, missed_ticks) = three_ticks_starting_in_the_past(MissedTickBehavior::Delay).await;
+
+	assert_eq!(
+		vec![
+			start - Duration::from_millis(3500),
+			start + Duration::from_secs(1),
+			start + Duration::from_secs(2),
+		],
+		ticks
+	);
+	assert_eq!(4, missed_ticks);
+}
+
+#[tokio::test]
+async fn should_skip_missed_ticks() {
+	let (start, ticks, missed_ticks) = three_ticks_starting_in_the_past(MissedTickBehavior::Skip).await;
+
+	assert_eq!(
+		vec![
+			start - Duration::from_millis(3500),
+			start + Duration::from_millis(500),
+			start + Duration::from_millis(1500),
+		],
+		ticks
+	);
+	assert_eq!(4, missed_ticks);
+}
+
+/// Returns the start, the first three ticks and the missed ticks of an interval with a period of 1s
+/// that started 3.5s before the start.
+async fn three_ticks_starting_in_the_past(missed_tick_behavior: MissedTickBehavior) -> (Instant, Vec<Instant>, u64) {
+	let timer_registry = Arc::new(TimerRegistry::builder().start_offset(Duration::from_secs(60)).build());
+	let start = timer_registry.now();
+
+	let mut interval = timer_registry.interval_at(start - Duration::from_millis(3500), Duration::from_secs(1));
+	interval.set_missed_tick_behavior(missed_tick_behavior);
+	let join_handle = tokio::spawn(async move {
+		let mut ticks = Vec::new();
+		for _ in 0..3 {
+			let (_guard, tick) = interval.tick().await;
+			ticks.push(tick);
+		}
+		(ticks, interval.missed_ticks())
+	});
+
+	timer_registry.advance_time(Duration::from_secs(2)).await;
+	let (ticks, missed_ticks) = join_handle.await.expect("Task panicked");
+	(start, ticks, missed_ticks)
+}
+
+#[tokio::test]
+async fn should_tick_at_the_deadline_it_has_been_reset_to() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	let start = timer_registry.now();
+	let mut interval = timer_registry.interval(Duration::from_secs(10));
+
+	interval.reset_after(Duration::from_secs(3));
+	assert_eq!(start + Duration::from_secs(3), interval.next_deadline());
+	let (_, tick) = tokio::join!(
+		timer_registry.advance_time(Duration::from_secs(5)),
+		next_tick(&mut interval)
+	);
+	assert_eq!(start + Duration::from_secs(3), tick);
+
+	interval.reset_immediately();
+	let (_, tick) = tokio::join!(
+		timer_registry.advance_time(Duration::from_secs(1)),
+		next_tick(&mut interval)
+	);
+	assert_eq!(start + Duration::from_secs(5), tick);
+
+	interval.reset_at(start + Duration::from_secs(7));
+	let (_, tick) = tokio::join!(
+		timer_registry.advance_time(Duration::from_secs(1)),
+		next_tick(&mut interval)
+	);
+	assert_eq!(start + Duration::from_secs(7), tick);
+
+	interval.reset();
+	let (_, tick) = tokio::join!(
+		timer_registry.advance_time(Duration::from_secs(10)),
+		next_tick(&mut interval)
+	);
+	assert_eq!(start + Duration::from_secs(17), tick);
+	assert_eq!(start + Duration::from_secs(27), interval.next_deadline());
+	assert_eq!(0, interval.missed_ticks());
+}
+
+async fn next_tick(interval: &mut Interval) -> Instant {
+	let (_guard, tick) = interval.tick().await;
+	tick
+}
@@ -1,9 +1,10 @@
-use async_time_mock_core::TimerRegistry;
+use async_time_mock_core::{IdleSummary, NoTimerScheduled, PendingTimer, TimerEvent, TimerKind, TimerRegistry};
 use futures_lite::future::poll_once;
+use std::future;
 use std::pin::pin;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use tokio::join;
 
 #[tokio::test]
@@ -318,3 +319,970 @@ async fn should_simulate_system_time() {
 		"Should have advanced system time by the given duration"
 	);
 }
+
+#[tokio::test]
+async fn advance_to_should_trigger_timers_up_to_and_including_the_given_instant() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	let start = timer_registry.now();
+	let sleep_counter = Arc::new(AtomicUsize::default());
+
+	let sleep_handles = (1..=3)
+		.map(|seconds| {
+			let timer_registry = timer_registry.clone();
+			let sleep_counter = sleep_counter.clone();
+			tokio::task::spawn(async move {
+				let _guard = timer_registry.sleep(Duration::from_secs(seconds)).await;
+				sleep_counter.fetch_add(1, Ordering::SeqCst);
+			})
+		})
+		.collect::<Vec<_>>();
+
+	timer_registry.advance_to(start + Duration::from_secs(2)).await;
+	assert_eq!(
+		2,
+		sleep_counter.load(Ordering::SeqCst),
+		"Timers up to and including the instant should have been triggered"
+	);
+	assert_eq!(start + Duration::from_secs(2), timer_registry.now());
+
+	timer_registry.advance_to(start + Duration::from_secs(3)).await;
+	assert_eq!(
+		3,
+		sleep_counter.load(Ordering::SeqCst),
+		"All timers should have been triggered"
+	);
+
+	for sleep_handle in sleep_handles {
+		sleep_handle.await.expect("Sleeping task crashed");
+	}
+}
+
+#[tokio::test]
+async fn advance_to_should_not_go_back_in_time() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	let start = timer_registry.now();
+
+	let _timer = timer_registry.sleep(Duration::from_secs(60));
+	timer_registry.advance_time(Duration::from_secs(10)).await;
+
+	timer_registry.advance_to(start + Duration::from_secs(5)).await;
+	assert_eq!(start + Duration::from_secs(10), timer_registry.now());
+}
+
+#[tokio::test]
+#[should_panic]
+async fn advance_to_should_panic_with_instant_from_different_timer_registry() {
+	let timer_registry = TimerRegistry::default();
+	let other_timer_registry = TimerRegistry::default();
+
+	timer_registry.advance_to(other_timer_registry.now()).await;
+}
+
+#[tokio::test]
+async fn advance_to_next_timer_should_trigger_the_next_timers_only() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	let start = timer_registry.now();
+	let sleep_counter = Arc::new(AtomicUsize::default());
+
+	let sleep_handles = [5, 5, 10]
+		.into_iter()
+		.map(|seconds| {
+			let timer_registry = timer_registry.clone();
+			let sleep_counter = sleep_counter.clone();
+			tokio::task::spawn(async move {
+				let _guard = timer_registry.sleep(Duration::from_secs(seconds)).await;
+				sleep_counter.fetch_add(1, Ordering::SeqCst);
+			})
+		})
+		.collect::<Vec<_>>();
+	// let the tasks schedule their timers
+	tokio::task::yield_now().await;
+
+	let now = timer_registry.advance_to_next_timer().await;
+	assert_eq!(Some(start + Duration::from_secs(5)), now);
+	assert_eq!(start + Duration::from_secs(5), timer_registry.now());
+	assert_eq!(
+		2,
+		sleep_counter.load(Ordering::SeqCst),
+		"Both timers scheduled for the same time should have been triggered"
+	);
+
+	let now = timer_registry.advance_to_next_timer().await;
+	assert_eq!(Some(start + Duration::from_secs(10)), now);
+	assert_eq!(
+		3,
+		sleep_counter.load(Ordering::SeqCst),
+		"All timers should have been triggered"
+	);
+
+	for sleep_handle in sleep_handles {
+		sleep_handle.await.expect("Sleeping task crashed");
+	}
+}
+
+#[tokio::test]
+async fn advance_to_next_timer_should_not_wait_if_no_timer_is_scheduled() {
+	let timer_registry = TimerRegistry::default();
+	let start = timer_registry.now();
+
+	assert_eq!(None, timer_registry.advance_to_next_timer().await);
+	assert_eq!(start, timer_registry.now());
+}
+
+#[tokio::test]
+async fn advance_until_idle_should_trigger_timers_scheduled_by_other_timers() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	let start = timer_registry.now();
+
+	let join_handle = tokio::spawn({
+		let timer_registry = timer_registry.clone();
+		async move {
+			let mut guard = timer_registry.sleep(Duration::from_secs(1)).await;
+			for _ in 1..5 {
+				// schedule the next timer before dropping the guard so the registry knows about it
+				let sleep = timer_registry.sleep(Duration::from_secs(1));
+				drop(guard);
+				guard = sleep.await;
+			}
+		}
+	});
+	// let the task schedule its first timer
+	tokio::task::yield_now().await;
+
+	let summary = timer_registry.advance_until_idle(None).await;
+	assert_eq!(
+		IdleSummary {
+			now: start + Duration::from_secs(5),
+			triggered_timers: 5,
+			is_idle: true,
+		},
+		summary
+	);
+
+	join_handle.await.expect("Sleeping task crashed");
+}
+
+#[tokio::test]
+async fn advance_until_idle_should_stop_at_max() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	let start = timer_registry.now();
+
+	let join_handle = tokio::spawn({
+		let timer_registry = timer_registry.clone();
+		async move {
+			let mut interval = timer_registry.interval(Duration::from_secs(1));
+			loop {
+				let _guard = interval.tick().await;
+			}
+		}
+	});
+	// let the task schedule its first timer
+	tokio::task::yield_now().await;
+
+	let summary = timer_registry
+		.advance_until_idle(Some(Duration::from_millis(10_500)))
+		.await;
+	assert_eq!(
+		IdleSummary {
+			now: start + Duration::from_millis(10_500),
+			triggered_timers: 11,
+			is_idle: false,
+		},
+		summary
+	);
+
+	join_handle.abort();
+}
+
+#[tokio::test]
+async fn advance_until_idle_should_not_wait_if_no_timer_is_scheduled() {
+	let timer_registry = TimerRegistry::default();
+	let start = timer_registry.now();
+
+	let summary = timer_registry.advance_until_idle(Some(Duration::from_secs(10))).await;
+	assert_eq!(
+		IdleSummary {
+			now: start,
+			triggered_timers: 0,
+			is_idle: true,
+		},
+		summary
+	);
+}
+
+#[tokio::test]
+async fn advance_time_without_waiting_should_advance_if_no_timer_is_scheduled() {
+	let timer_registry = TimerRegistry::default();
+	let start = timer_registry.now();
+
+	timer_registry
+		.advance_time_without_waiting(Duration::from_secs(10))
+		.await;
+	assert_eq!(start + Duration::from_secs(10), timer_registry.now());
+}
+
+#[tokio::test]
+async fn advance_time_with_timeout_should_fail_if_no_timer_is_scheduled() {
+	let timer_registry = TimerRegistry::default();
+	let start = timer_registry.now();
+
+	let result = timer_registry
+		.advance_time_with_timeout(Duration::from_secs(10), Duration::from_millis(10))
+		.await;
+	assert_eq!(
+		Err(NoTimerScheduled {
+			timeout: Duration::from_millis(10)
+		}),
+		result
+	);
+	assert_eq!(start, timer_registry.now(), "Should not have advanced the time");
+}
+
+#[tokio::test]
+async fn advance_time_with_timeout_should_advance_once_a_timer_is_scheduled() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	let start = timer_registry.now();
+	let has_slept = Arc::new(AtomicBool::default());
+
+	let join_handle = tokio::spawn({
+		let timer_registry = timer_registry.clone();
+		let has_slept = has_slept.clone();
+		async move {
+			let _guard = timer_registry.sleep(Duration::from_secs(10)).await;
+			has_slept.store(true, Ordering::SeqCst);
+		}
+	});
+
+	timer_registry
+		.advance_time_with_timeout(Duration::from_secs(10), Duration::from_secs(10))
+		.await
+		.expect("Timer should have been scheduled");
+	assert_eq!(start + Duration::from_secs(10), timer_registry.now());
+	assert!(
+		has_slept.load(Ordering::SeqCst),
+		"Should have slept after advancing time"
+	);
+
+	join_handle.await.expect("Sleeping task crashed");
+}
+
+#[tokio::test]
+#[should_panic(
+	expected = "TimeHandlerGuards haven't been dropped within 10ms for the following timers:\n* Sleep timer #1"
+)]
+async fn advance_time_should_panic_if_time_handler_guard_is_held_longer_than_the_timeout() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	timer_registry.set_time_handler_timeout(Some(Duration::from_millis(10)));
+
+	let finishing_handle = tokio::spawn({
+		let timer_registry = timer_registry.clone();
+		async move {
+			let _guard = timer_registry.sleep(Duration::from_secs(1)).await;
+		}
+	});
+	let hanging_handle = tokio::spawn({
+		let timer_registry = timer_registry.clone();
+		async move {
+			let _guard = timer_registry.sleep(Duration::from_secs(1)).await;
+			future::pending::<()>().await;
+		}
+	});
+	// let the tasks schedule their timers
+	tokio::task::yield_now().await;
+
+	timer_registry.advance_time(Duration::from_secs(1)).await;
+
+	finishing_handle.await.expect("Sleeping task crashed");
+	hanging_handle.abort();
+}
+
+#[tokio::test]
+async fn advance_time_should_not_panic_if_time_handler_guard_is_dropped_within_the_timeout() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	timer_registry.set_time_handler_timeout(Some(Duration::from_secs(10)));
+
+	let join_handle = tokio::spawn({
+		let timer_registry = timer_registry.clone();
+		async move {
+			let _guard = timer_registry.sleep(Duration::from_secs(1)).await;
+		}
+	});
+
+	timer_registry.advance_time(Duration::from_secs(1)).await;
+
+	join_handle.await.expect("Sleeping task crashed");
+}
+
+#[tokio::test]
+async fn should_list_pending_timers() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	let start = timer_registry.now();
+
+	let _interval = timer_registry.interval_at(start + Duration::from_secs(10), Duration::from_secs(1));
+	let _timeout = timer_registry.timeout(Duration::from_secs(5), future::pending::<()>());
+	let _sleep = timer_registry.sleep(Duration::from_secs(5));
+
+	assert_eq!(
+		vec![
+			PendingTimer {
+				deadline: start + Duration::from_secs(5),
+				sequence: 1,
+				kind: TimerKind::Timeout,
+				label: None,
+			},
+			PendingTimer {
+				deadline: start + Duration::from_secs(5),
+				sequence: 2,
+				kind: TimerKind::Sleep,
+				label: None,
+			},
+			PendingTimer {
+				deadline: start + Duration::from_secs(10),
+				sequence: 0,
+				kind: TimerKind::Interval,
+				label: None,
+			},
+		],
+		timer_registry.pending_timers()
+	);
+	assert_eq!(3, timer_registry.pending_timer_count());
+	assert_eq!(Some(start + Duration::from_secs(5)), timer_registry.next_deadline());
+}
+
+#[tokio::test]
+async fn should_not_list_timers_that_have_been_triggered() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+
+	let join_handle = tokio::spawn({
+		let timer_registry = timer_registry.clone();
+		async move {
+			let _guard = timer_registry.sleep(Duration::from_secs(1)).await;
+		}
+	});
+
+	timer_registry.advance_time(Duration::from_secs(1)).await;
+
+	assert_eq!(Vec::<PendingTimer>::new(), timer_registry.pending_timers());
+	assert_eq!(0, timer_registry.pending_timer_count());
+	assert_eq!(None, timer_registry.next_deadline());
+
+	join_handle.await.expect("Sleeping task crashed");
+}
+
+#[tokio::test]
+async fn should_list_labels_of_pending_timers() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	let start = timer_registry.now();
+
+	let _sleep = timer_registry.sleep_labelled(Duration::from_secs(1), "sleep");
+	let _sleep_until = timer_registry.sleep_until_labelled(start + Duration::from_secs(2), "sleep_until");
+	let _timeout = timer_registry.timeout_labelled(Duration::from_secs(3), future::pending::<()>(), "timeout");
+	let _timeout_at =
+		timer_registry.timeout_at_labelled(start + Duration::from_secs(4), future::pending::<()>(), "timeout_at");
+	let _interval = timer_registry.interval_labelled(Duration::from_secs(5), "interval");
+	let _interval_at =
+		timer_registry.interval_at_labelled(start + Duration::from_secs(6), Duration::from_secs(5), "interval_at");
+
+	let labels = timer_registry
+		.pending_timers()
+		.into_iter()
+		.map(|timer| timer.label.expect("Timer should have had a label").to_string())
+		.collect::<Vec<_>>();
+	assert_eq!(
+		vec![
+			"interval",
+			"sleep",
+			"sleep_until",
+			"timeout",
+			"timeout_at",
+			"interval_at",
+		],
+		labels
+	);
+	assert!(
+		format!("{timer_registry:?}").contains("\"timeout_at\""),
+		"Labels should be part of the Debug output"
+	);
+}
+
+#[tokio::test]
+async fn should_keep_the_label_of_an_interval_after_ticking() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+
+	let join_handle = tokio::spawn({
+		let timer_registry = timer_registry.clone();
+		async move {
+			let mut interval = timer_registry.interval_labelled(Duration::from_secs(1), "heartbeat");
+			let _guard = interval.tick().await;
+			interval
+		}
+	});
+
+	timer_registry.advance_time(Duration::ZERO).await;
+	let _interval = join_handle.await.expect("Interval task crashed");
+
+	let pending_timer = timer_registry
+		.pending_timers()
+		.pop()
+		.expect("Interval should have scheduled the next tick");
+	assert_eq!(Some("heartbeat".into()), pending_timer.label);
+}
+
+#[tokio::test]
+#[should_panic(expected = "* Sleep timer #0 (\"hanging\") scheduled at 1s")]
+async fn advance_time_should_show_labels_when_time_handler_guard_is_held_longer_than_the_timeout() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	timer_registry.set_time_handler_timeout(Some(Duration::from_millis(10)));
+
+	let hanging_handle = tokio::spawn({
+		let timer_registry = timer_registry.clone();
+		async move {
+			let _guard = timer_registry.sleep_labelled(Duration::from_secs(1), "hanging").await;
+			future::pending::<()>().await;
+		}
+	});
+
+	timer_registry.advance_time(Duration::from_secs(1)).await;
+
+	hanging_handle.abort();
+}
+
+#[tokio::test]
+async fn should_not_record_events_by_default() {
+	let timer_registry = TimerRegistry::default();
+
+	let _sleep = timer_registry.sleep(Duration::from_secs(1));
+	timer_registry.advance_time(Duration::from_millis(500)).await;
+
+	assert_eq!(Vec::<TimerEvent>::new(), timer_registry.event_log());
+}
+
+#[tokio::test]
+async fn should_record_events() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	timer_registry.set_event_log_capacity(Some(100));
+	let start = timer_registry.now();
+
+	let join_handle = tokio::spawn({
+		let timer_registry = timer_registry.clone();
+		async move {
+			let _guard = timer_registry.sleep_labelled(Duration::from_secs(1), "sleep").await;
+		}
+	});
+	timer_registry.advance_time(Duration::from_secs(2)).await;
+	join_handle.await.expect("Sleeping task crashed");
+
+	let timer = PendingTimer {
+		deadline: start + Duration::from_secs(1),
+		sequence: 0,
+		kind: TimerKind::Sleep,
+		label: Some("sleep".into()),
+	};
+	assert_eq!(
+		vec![
+			TimerEvent::Scheduled(timer.clone()),
+			TimerEvent::TimeAdvanced {
+				from: start,
+				to: start + Duration::from_secs(1),
+			},
+			TimerEvent::Triggered(timer.clone()),
+			TimerEvent::HandlerFinished(timer),
+			TimerEvent::TimeAdvanced {
+				from: start + Duration::from_secs(1),
+				to: start + Duration::from_secs(2),
+			},
+		],
+		timer_registry.event_log()
+	);
+}
+
+#[tokio::test]
+async fn should_produce_the_same_timeline_for_the_same_operations() {
+	async fn timeline() -> Vec<String> {
+		let timer_registry = Arc::new(TimerRegistry::default());
+		timer_registry.set_event_log_capacity(Some(100));
+
+		let join_handle = tokio::spawn({
+			let timer_registry = timer_registry.clone();
+			async move {
+				let mut interval = timer_registry.interval_labelled(Duration::from_secs(1), "interval");
+				for _ in 0..3 {
+					let _guard = interval.tick().await;
+				}
+			}
+		});
+		timer_registry.advance_time(Duration::from_millis(2500)).await;
+		join_handle.await.expect("Interval task crashed");
+
+		timer_registry.event_log().iter().map(ToString::to_string).collect()
+	}
+
+	let first_timeline = timeline().await;
+	let second_timeline = timeline().await;
+
+	assert_eq!(first_timeline, second_timeline);
+	assert_eq!(
+		"scheduled: Interval timer #0 (\"interval\") scheduled at 0ns",
+		first_timeline[0],
+	);
+	assert_eq!("time advanced: 2s -> 2.5s", first_timeline[first_timeline.len() - 1]);
+}
+
+#[cfg(feature = "tracing-subscriber")]
+#[tokio::test]
+async fn format_time_should_show_the_mocked_system_time() {
+	use tracing_subscriber::fmt::format::Writer;
+	use tracing_subscriber::fmt::time::FormatTime;
+
+	let timer_registry = TimerRegistry::default();
+	timer_registry
+		.advance_time_without_waiting(Duration::from_secs(3 * 60 * 60) + Duration::from_millis(1))
+		.await;
+
+	let mut formatted = String::new();
+	timer_registry
+		.format_time(&mut Writer::new(&mut formatted))
+		.expect("Failed to format time");
+	assert_eq!("1970-01-01T03:00:00.001000Z", formatted);
+}
+
+#[cfg(all(feature = "tracing", feature = "tracing-subscriber"))]
+#[test]
+fn tracing_with_the_mocked_time_should_not_deadlock() {
+	use async_time_mock_core::MockFormatTime;
+	use std::sync::mpsc;
+	use tracing_subscriber::filter::LevelFilter;
+
+	let timer_registry = Arc::new(TimerRegistry::default());
+	let subscriber = tracing_subscriber::fmt()
+		.with_max_level(LevelFilter::TRACE)
+		.with_timer(MockFormatTime(timer_registry.clone()))
+		.with_writer(std::io::sink)
+		.finish();
+
+	// run on a separate thread, so a deadlock fails the test instead of hanging it
+	let (finished_sender, finished_receiver) = mpsc::channel();
+	std::thread::spawn(move || {
+		let _subscriber = tracing::subscriber::set_default(subscriber);
+		let runtime = tokio::runtime::Builder::new_current_thread()
+			.build()
+			.expect("Failed to build runtime");
+		runtime.block_on(async {
+			let sleep = timer_registry.sleep(Duration::from_secs(1));
+			join!(timer_registry.advance_time(Duration::from_secs(2)), async {
+				drop(sleep.await);
+			});
+			timer_registry
+				.advance_time_without_waiting(Duration::from_secs(1))
+				.await;
+			timer_registry.jump_system_time_forward(Duration::from_secs(60));
+		});
+		let _ = finished_sender.send(());
+	});
+
+	finished_receiver
+		.recv_timeout(Duration::from_secs(10))
+		.expect("Advancing the time deadlocked while tracing");
+}
+
+#[cfg(feature = "tracing-subscriber")]
+#[test]
+fn mock_format_time_should_format_a_shared_timer_registry() {
+	use async_time_mock_core::MockFormatTime;
+	use tracing_subscriber::fmt::format::Writer;
+	use tracing_subscriber::fmt::time::FormatTime;
+
+	let timer_registry = Arc::new(TimerRegistry::default());
+	let format_time = MockFormatTime(timer_registry.clone());
+
+	let mut formatted = String::new();
+	format_time
+		.format_time(&mut Writer::new(&mut formatted))
+		.expect("Failed to format time");
+	assert_eq!("1970-01-01T00:00:00.000000Z", formatted);
+}
+
+#[tokio::test]
+async fn auto_advance_should_advance_to_timers_once_the_tasks_are_idle() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	let start = timer_registry.now();
+
+	let auto_advance = tokio::spawn({
+		let timer_registry = timer_registry.clone();
+		async move { timer_registry.auto_advance(tokio::task::yield_now).await }
+	});
+
+	drop(timer_registry.sleep(Duration::from_secs(60 * 60)).await);
+	drop(timer_registry.sleep(Duration::from_secs(2 * 60 * 60)).await);
+	assert_eq!(
+		Duration::from_secs(3 * 60 * 60),
+		timer_registry.now().duration_since(start)
+	);
+
+	auto_advance.abort();
+}
+
+#[tokio::test]
+async fn auto_advance_should_not_overtake_tasks_within_the_configured_idle_yields() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	timer_registry.set_auto_advance_idle_yields(100);
+
+	let auto_advance = tokio::spawn({
+		let timer_registry = timer_registry.clone();
+		async move { timer_registry.auto_advance(tokio::task::yield_now).await }
+	});
+	let busy_task = async {
+		for _ in 0..40 {
+			tokio::task::yield_now().await;
+		}
+	};
+
+	let result = timer_registry.timeout(Duration::from_secs(10), busy_task).await;
+	assert!(result.is_ok(), "Auto advance overtook the busy task");
+
+	auto_advance.abort();
+}
+
+#[tokio::test]
+async fn auto_advance_should_wait_for_timers_to_be_scheduled() {
+	let timer_registry = Arc::new(TimerRegistry::default());
+	let start = timer_registry.now();
+
+	let auto_advance = tokio::spawn({
+		let timer_registry = timer_registry.clone();
+		async move { timer_registry.auto_advance(tokio::task::yield_now).await }
+	});
+	for _ in 0..100 {
+		tokio::task::yield_now().await;
+	}
+	assert_eq!(start, timer_registry.now());
+
+	drop(timer_registry.sleep(Duration::from_secs(1)).await);
+	assert_eq!(Duration::from_secs(1), timer_registry.now().duration_since(start));
+
+	auto_advance.abort();
+}
+
+#[tokio::test]
+async fn system_time_should_start_at_the_configured_start_system_time() {
+	let start_system_time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+	let timer_registry = TimerRegistry::builder().start_system_time(start_system_time).build();
+	assert_eq!(start_system_time, timer_registry.system_time());
+
+	timer_registry
+		.advance_time_without_waiting(Duration::from_secs(42))
+		.await;
+	assert_eq!(
+		start_system_time + Duration::from_secs(42),
+		timer_registry.system_time()
+	);
+}
+
+#[tokio::test]
+async fn start_offset_should_allow_instants_before_the_start() {
+	let timer_registry = TimerRegistry::builder().start_offset(Duration::from_secs(60)).build();
+	let start = timer_registry.now();
+
+	let before_start = start - Duration::from_secs(30);
+	assert_eq!(Duration::from_secs(30), start.duration_since(before_start));
+	assert_eq!(SystemTime::UNIX_EPOCH, timer_registry.system_time());
+}
+
+#[tokio::test]
+async fn system_time_should_jump_without_triggering_timers() {
+	let timer_registry = TimerRegistry::default();
+	let start = timer_registry.now();
+	let mut sleep = pin!(timer_registry.sleep(Duration::from_secs(10)));
+
+	timer_registry.jump_system_time_forward(Duration::from_secs(60 * 60));
+	assert_eq!(
+		SystemTime::UNIX_EPOCH + Duration::from_secs(60 * 60),
+		timer_registry.system_time()
+	);
+	assert_eq!(start, timer_registry.now());
+	assert!(
+		poll_once(sleep.as_mut()).await.is_none(),
+		"Jumping must not trigger timers"
+	);
+
+	timer_registry.jump_system_time_backward(Duration::from_secs(2 * 60 * 60));
+	assert_eq!(
+		SystemTime::UNIX_EPOCH - Duration::from_secs(60 * 60),
+		timer_registry.system_time()
+	);
+	assert_eq!(start, timer_registry.now());
+	assert!(
+		poll_once(sleep.as_mut()).await.is_none(),
+		"Jumping must not trigger timers"
+	);
+}
+
+#[tokio::test]
+async fn system_time_should_keep_ticking_after_a_jump() {
+	let timer_registry = TimerRegistry::default();
+	timer_registry.set_event_log_capacity(Some(10));
+
+	let jumped_to = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+	timer_registry.set_system_time(jumped_to);
+	timer_registry
+		.advance_time_without_waiting(Duration::from_secs(5))
+		.await;
+
+	assert_eq!(jumped_to + Duration::from_secs(5), timer_registry.system_time());
+	assert_eq!(
+		TimerEvent::SystemTimeJumped {
+			from: SystemTime::UNIX_EPOCH,
+			to: jumped_to,
+		},
+		timer_registry.event_log()[0]
+	);
+}
+
+#[tokio::test]
+async fn jumping_the_system_time_should_not_lose_concurrently_advanced_time() {
+	const STEPS: u64 = 100_000;
+	let timer_registry = Arc::new(TimerRegistry::default());
+	let start = timer_registry.system_time();
+
+	let jumping_thread = std::thread::spawn({
+		let timer_registry = timer_registry.clone();
+		move || {
+			for _ in 0..STEPS {
+				timer_registry.jump_system_time_forward(Duration::from_secs(1));
+			}
+		}
+	});
+	for _ in 0..STEPS {
+		timer_registry
+			.advance_time_without_waiting(Duration::from_secs(1))
+			.await;
+	}
+	jumping_thread.join().expect("Jumping thread panicked");
+
+	assert_eq!(start + Duration::from_secs(2 * STEPS), timer_registry.system_time());
+}
+
+#[tokio::test]
+async fn sleep_until_system_time_should_finish_once_the_system_time_is_reached() {
+	let timer_registry = TimerRegistry::builder()
+		.start_system_time(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+		.build();
+	let start = timer_registry.now();
+	let until = timer_registry.system_time() + Duration::from_secs(60);
+	let mut sleep = pin!(timer_registry.sleep_until_system_time(until));
+
+	assert_eq!(Some(start + Duration::from_secs(60)), timer_registry.next_deadline());
+	timer_registry.advance_time(Duration::from_secs(59)).await;
+	assert!(
+		poll_once(sleep.as_mut()).await.is_none(),
+		"Timer must not have expired yet"
+	);
+
+	join!(timer_registry.advance_time(Duration::from_secs(1)), async {
+		drop(sleep.await);
+	});
+	assert_eq!(until, timer_registry.system_time());
+}
+
+#[tokio::test]
+async fn sleep_until_system_time_should_finish_on_the_next_advance_after_jumping_past_it() {
+	let timer_registry = TimerRegistry::default();
+	timer_registry.set_event_log_capacity(Some(10));
+	let start = timer_registry.now();
+	let mut sleep = pin!(timer_registry
+		.sleep_until_system_time_labelled(SystemTime::UNIX_EPOCH + Duration::from_secs(60 * 60), "02:00 UTC",));
+
+	timer_registry.jump_system_time_forward(Duration::from_secs(2 * 60 * 60));
+	assert!(
+		poll_once(sleep.as_mut()).await.is_none(),
+		"Jumping must not trigger timers"
+	);
+
+	join!(timer_registry.advance_time(Duration::from_secs(1)), async {
+		drop(sleep.await);
+	});
+	assert_eq!(start + Duration::from_secs(1), timer_registry.now());
+	assert!(timer_registry.event_log().iter().any(|event| matches!(
+		event,
+		TimerEvent::Triggered(PendingTimer { deadline, .. }) if *deadline == start
+	)));
+}
+
+#[tokio::test]
+async fn sleep_until_system_time_should_be_delayed_by_jumping_backward() {
+	let timer_registry = TimerRegistry::default();
+	let mut sleep = pin!(timer_registry.sleep_until_system_time(SystemTime::UNIX_EPOCH + Duration::from_secs(10)));
+
+	timer_registry.jump_system_time_backward(Duration::from_secs(10));
+	timer_registry.advance_time(Duration::from_secs(10)).await;
+	assert!(
+		poll_once(sleep.as_mut()).await.is_none(),
+		"Timer must not have expired yet"
+	);
+
+	join!(timer_registry.advance_time(Duration::from_secs(10)), async {
+		drop(sleep.await);
+	});
+}
+
+#[test]
+fn sleep_until_system_time_should_be_cancelled_when_dropped() {
+	let timer_registry = TimerRegistry::default();
+	let sleep = timer_registry.sleep_until_system_time(SystemTime::UNIX_EPOCH + Duration::from_secs(10));
+	assert_eq!(1, timer_registry.pending_timer_count());
+
+	drop(sleep);
+	assert_eq!(0, timer_registry.pending_timer_count());
+}
+
+#[tokio::test]
+async fn should_remove_timer_when_listener_is_dropped() {
+	let timer_registry = TimerRegistry::default();
+
+	let listener = timer_registry.sleep(Duration::from_secs(1));
+	assert_eq!(1, timer_registry.pending_timer_count());
+
+	drop(listener);
+	assert_eq!(0, timer_registry.pending_timer_count());
+}
+
+#[tokio::test]
+async fn should_only_remove_the_dropped_timer_when_scheduled_at_the_same_time() {
+	let timer_registry = TimerRegistry::default();
+
+	let first = timer_registry.sleep(Duration::from_secs(1));
+	let second = timer_registry.sleep(Duration::from_secs(1));
+	let third = timer_registry.sleep(Duration::from_secs(1));
+
+	drop(second);
+	assert_eq!(2, timer_registry.pending_timer_count());
+
+	drop(first);
+	drop(third);
+	assert_eq!(0, timer_registry.pending_timer_count());
+}
+
+#[tokio::test]
+async fn should_be_empty_after_timeouts_finished_early() {
+	let timer_registry = TimerRegistry::default();
+
+	for seconds in 1..=1000 {
+		timer_registry
+			.timeout(Duration::from_secs(seconds), future::ready(()))
+			.await
+			.expect("Timeout should not have elapsed");
+	}
+
+	assert_eq!(0, timer_registry.pending_timer_count());
+}
+
+#[tokio::test]
+async fn should_not_fail_when_listener_is_dropped_after_the_timer_registry() {
+	let timer_registry = TimerRegistry::default();
+
+	let listener = timer_registry.sleep(Duration::from_secs(1));
+	drop(timer_registry);
+	drop(listener);
+}
+
+#[tokio::test]
+async fn timer_listener_reset_should_move_a_pending_timer() {
+	let timer_registry = TimerRegistry::default();
+	let start = timer_registry.now();
+	let mut sleep = pin!(timer_registry.sleep(Duration::from_secs(10)));
+	assert_eq!(start + Duration::from_secs(10), sleep.deadline());
+
+	sleep.as_mut().reset(start + Duration::from_secs(20));
+	assert_eq!(start + Duration::from_secs(20), sleep.deadline());
+	assert_eq!(1, timer_registry.pending_timer_count());
+
+	timer_registry.advance_time(Duration::from_secs(10)).await;
+	assert!(!sleep.is_elapsed());
+	assert!(
+		poll_once(sleep.as_mut()).await.is_none(),
+		"Timer must not have expired yet"
+	);
+
+	join!(timer_registry.advance_time(Duration::from_secs(10)), async {
+		drop(sleep.as_mut().await);
+	});
+	assert!(sleep.is_elapsed());
+}
+
+#[tokio::test]
+async fn timer_listener_reset_should_rearm_an_expired_timer() {
+	let timer_registry = TimerRegistry::default();
+	let start = timer_registry.now();
+	let mut sleep = pin!(timer_registry.sleep(Duration::from_secs(10)));
+	join!(timer_registry.advance_time(Duration::from_secs(10)), async {
+		drop(sleep.as_mut().await);
+	});
+	assert!(sleep.is_elapsed());
+
+	sleep.as_mut().reset(start + Duration::from_secs(15));
+	assert!(!sleep.is_elapsed());
+	assert_eq!(Some(start + Duration::from_secs(15)), timer_registry.next_deadline());
+
+	join!(timer_registry.advance_time(Duration::from_secs(5)), async {
+		drop(sleep.as_mut().await);
+	});
+	assert!(sleep.is_elapsed());
+	assert_eq!(0, timer_registry.pending_timer_count());
+}
+
+#[tokio::test]
+async fn timer_listener_reset_should_keep_the_order_timers_were_scheduled_in() {
+	let timer_registry = TimerRegistry::default();
+	timer_registry.set_event_log_capacity(Some(100));
+	let start = timer_registry.now();
+	let mut first = pin!(timer_registry.sleep_labelled(Duration::from_secs(1), "first"));
+	let mut second = pin!(timer_registry.sleep_labelled(Duration::from_secs(2), "second"));
+
+	first.as_mut().reset(start + Duration::from_secs(2));
+	let pending_labels = timer_registry
+		.pending_timers()
+		.into_iter()
+		.map(|pending_timer| pending_timer.label.as_deref().map(ToOwned::to_owned))
+		.collect::<Vec<_>>();
+	assert_eq!(
+		vec![Some("first".to_owned()), Some("second".to_owned())],
+		pending_labels
+	);
+
+	join!(
+		timer_registry.advance_time(Duration::from_secs(2)),
+		async { drop(first.as_mut().await) },
+		async { drop(second.as_mut().await) },
+	);
+	let triggered_labels = timer_registry
+		.event_log()
+		.into_iter()
+		.filter_map(|event| match event {
+			TimerEvent::Triggered(pending_timer) => pending_timer.label.as_deref().map(ToOwned::to_owned),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(vec!["first".to_owned(), "second".to_owned()], triggered_labels);
+}
+
+#[tokio::test]
+async fn timeout_reset_should_extend_the_deadline_and_keep_the_future() {
+	let timer_registry = TimerRegistry::default();
+	let start = timer_registry.now();
+	let mut timeout = pin!(timer_registry.timeout(Duration::from_secs(10), future::pending::<()>()));
+	assert_eq!(start + Duration::from_secs(10), timeout.deadline());
+
+	timer_registry.advance_time(Duration::from_secs(5)).await;
+	timeout.as_mut().reset(start + Duration::from_secs(15));
+	timer_registry.advance_time(Duration::from_secs(5)).await;
+	assert!(
+		poll_once(timeout.as_mut()).await.is_none(),
+		"Timeout must not have expired yet"
+	);
+
+	join!(timer_registry.advance_time(Duration::from_secs(5)), async {
+		let result = timeout.as_mut().await;
+		assert!(result.is_err(), "Timeout should have expired");
+	});
+	assert_eq!(start + Duration::from_secs(15), timeout.deadline());
+}
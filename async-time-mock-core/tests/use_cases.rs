@@ -68,14 +68,17 @@ async fn operation_with_timeout_triggered_by_interval() {
 		"Should have performed the operation 10 times once the time is over"
 	);
 
-	timer_registry.advance_time(Duration::from_secs(1)).await;
+	join_handle.await.expect("Task performing operations crashed");
+
+	// The interval has been cancelled together with the task, so there is no timer left to wait for
+	timer_registry
+		.advance_time_without_waiting(Duration::from_secs(1))
+		.await;
 	assert_eq!(
 		10,
 		operation_count.load(Ordering::SeqCst),
 		"Should not have performed the operation more than 10 times"
 	);
-
-	join_handle.await.expect("Task performing operations crashed");
 }
 
 #[tokio::test]
@@ -0,0 +1,32 @@
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+// NOTE That the can't implement PartialEq, Eq, Clone or Copy, because TimeHandlerGuard doesn't support that.
+#[must_use = "Elapsed must only be dropped once all side-effects of the timeout have been handled."]
+#[derive(Debug)]
+pub enum Elapsed {
+	/// smol doesn't have its own error type for elapsed timeouts.
+	Real,
+	#[cfg(feature = "mock")]
+	Mock(async_time_mock_core::Elapsed),
+}
+
+impl Display for Elapsed {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+		use Elapsed::*;
+		match self {
+			Real => formatter.write_str("deadline has elapsed"),
+			#[cfg(feature = "mock")]
+			Mock(elapsed) => Display::fmt(elapsed, formatter),
+		}
+	}
+}
+
+impl Error for Elapsed {}
+
+#[cfg(feature = "mock")]
+impl From<async_time_mock_core::Elapsed> for Elapsed {
+	fn from(elapsed: async_time_mock_core::Elapsed) -> Self {
+		Self::Mock(elapsed)
+	}
+}
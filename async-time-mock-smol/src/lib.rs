@@ -7,10 +7,28 @@ mod instant;
 pub use instant::Instant;
 
 mod timer;
+#[cfg(feature = "mock")]
+pub use timer::MockOneShot;
 pub use timer::Timer;
 
+mod sleep;
+pub use sleep::Sleep;
+
+mod elapsed;
+pub use elapsed::Elapsed;
+
+mod timeout;
+pub use timeout::Timeout;
+
+#[cfg(feature = "schedule")]
+mod schedule;
+#[cfg(feature = "schedule")]
+pub use schedule::Schedule;
+
 #[cfg(feature = "mock")]
 pub use async_time_mock_core as core;
+#[cfg(feature = "schedule")]
+pub use async_time_mock_core::{CronSchedule, InvalidCronSchedule};
 
 #[derive(Clone)]
 pub enum MockableClock {
@@ -33,12 +51,51 @@ impl From<async_time_mock_core::TimeHandlerGuard> for TimeHandlerGuard {
 }
 
 impl MockableClock {
+	/// Creates a mocked clock and the [`TimerRegistry`](async_time_mock_core::TimerRegistry) to control it with.
+	/// The mocked time starts at the defaults of [`TimerRegistryBuilder`](async_time_mock_core::TimerRegistryBuilder),
+	/// use [`mock_with`](Self::mock_with) to e.g. start at a non-zero offset so instants before the start can be represented.
 	#[cfg(feature = "mock")]
 	pub fn mock() -> (Self, std::sync::Arc<async_time_mock_core::TimerRegistry>) {
 		let timer_registry = std::sync::Arc::new(async_time_mock_core::TimerRegistry::default());
 		(Self::Mock(timer_registry.clone()), timer_registry)
 	}
 
+	/// Same as [`mock`](Self::mock), but with a [`TimerRegistry`](async_time_mock_core::TimerRegistry)
+	/// configured by `builder`, e.g. to start at a different system time.
+	#[cfg(feature = "mock")]
+	pub fn mock_with(
+		builder: async_time_mock_core::TimerRegistryBuilder,
+	) -> (Self, std::sync::Arc<async_time_mock_core::TimerRegistry>) {
+		let timer_registry = std::sync::Arc::new(builder.build());
+		(Self::Mock(timer_registry.clone()), timer_registry)
+	}
+
+	/// Advances the mocked time to `until`, see [`async_time_mock_core::TimerRegistry::advance_to`].
+	///
+	/// # Panics
+	/// If the clock isn't mocked or `until` wasn't created by this clock.
+	#[cfg(feature = "mock")]
+	pub async fn advance_to(&self, until: Instant) {
+		match (self, until) {
+			(MockableClock::Mock(registry), Instant::Mock(until)) => registry.advance_to(until).await,
+			_ => panic!("Only a mocked clock can be advanced and only to a mocked instant"),
+		}
+	}
+
+	/// Advances the mocked time to the next timer whenever the other tasks look idle,
+	/// see [`async_time_mock_core::TimerRegistry::auto_advance`] for how idle tasks are detected and its limits.
+	/// This never finishes, so spawn it as a separate task (e.g. with `smol::spawn`) and drop it once done.
+	///
+	/// # Panics
+	/// If the clock isn't mocked.
+	#[cfg(feature = "mock")]
+	pub async fn auto_advance(&self) -> ! {
+		match self {
+			MockableClock::Mock(registry) => registry.auto_advance(futures_lite::future::yield_now).await,
+			MockableClock::Real => panic!("Only a mocked clock can be advanced"),
+		}
+	}
+
 	pub fn now(&self) -> Instant {
 		use MockableClock::*;
 		match self {
@@ -57,34 +114,79 @@ impl MockableClock {
 		}
 	}
 
-	pub fn sleep(&self, duration: Duration) -> impl Future<Output = TimeHandlerGuard> + Send + 'static {
-		let clock = self.clone();
-		async move {
-			use MockableClock::*;
-			match clock {
-				Real => {
-					async_io::Timer::after(duration).await;
-					TimeHandlerGuard::Real
-				}
-				#[cfg(feature = "mock")]
-				Mock(registry) => registry.sleep(duration).await.into(),
-			}
+	pub fn sleep(&self, duration: Duration) -> Sleep {
+		use MockableClock::*;
+		match self {
+			Real => Sleep::real(real_deadline_after(duration)),
+			#[cfg(feature = "mock")]
+			Mock(registry) => registry.sleep(duration).into(),
+		}
+	}
+
+	pub fn sleep_until(&self, until: Instant) -> Sleep {
+		match (self, until) {
+			(MockableClock::Real, Instant::Real(until)) => Sleep::real(until),
+			#[cfg(feature = "mock")]
+			(MockableClock::Mock(registry), Instant::Mock(until)) => registry.sleep_until(until).into(),
+			#[cfg(feature = "mock")]
+			_ => panic!("Clock and instant weren't compatible, both need to be either real or mocked"),
 		}
 	}
 
-	pub fn sleep_until(&self, until: Instant) -> impl Future<Output = TimeHandlerGuard> + Send + 'static {
-		let clock = self.clone();
-		async move {
-			match (clock, until) {
-				(MockableClock::Real, Instant::Real(until)) => {
-					async_io::Timer::at(until).await;
-					TimeHandlerGuard::Real
-				}
-				#[cfg(feature = "mock")]
-				(MockableClock::Mock(registry), Instant::Mock(until)) => registry.sleep_until(until).await.into(),
-				#[cfg(feature = "mock")]
-				_ => panic!("Clock and instant weren't compatible, both need to be either real or mocked"),
+	/// Sleeps until the [`system_time`](Self::system_time) reaches `until`.
+	///
+	/// When mocked, this follows jumps of the mocked system time, see
+	/// [`async_time_mock_core::TimerRegistry::sleep_until_system_time`]. The real clock only computes how long to sleep
+	/// once and won't notice if the system time jumps in the meantime.
+	pub fn sleep_until_system_time(&self, until: SystemTime) -> Sleep {
+		use MockableClock::*;
+		match self {
+			Real => Sleep::real(real_deadline_after(
+				until.duration_since(SystemTime::now()).unwrap_or_default(),
+			)),
+			#[cfg(feature = "mock")]
+			Mock(registry) => registry.sleep_until_system_time(until).into(),
+		}
+	}
+
+	/// Ticks whenever the [`system_time`](Self::system_time) matches `cron_schedule`,
+	/// e.g. `clock.schedule("0 */5 * * * *".parse()?)` for every five minutes.
+	/// When mocked, the ticks follow jumps of the mocked system time.
+	#[cfg(feature = "schedule")]
+	pub fn schedule(&self, cron_schedule: CronSchedule) -> Schedule {
+		Schedule::new(self.clone(), cron_schedule)
+	}
+
+	/// Equivalent to [`async_io::Timer::never`], a timer that never fires.
+	pub fn never(&self) -> Timer {
+		use MockableClock::*;
+		match self {
+			Real => async_io::Timer::never().into(),
+			#[cfg(feature = "mock")]
+			Mock(registry) => timer::MockOneShot::new(registry.clone(), None).into(),
+		}
+	}
+
+	/// Equivalent to [`async_io::Timer::after`], a timer that fires once after `duration`.
+	pub fn after(&self, duration: Duration) -> Timer {
+		use MockableClock::*;
+		match self {
+			Real => async_io::Timer::after(duration).into(),
+			#[cfg(feature = "mock")]
+			Mock(registry) => timer::MockOneShot::new(registry.clone(), Some(registry.now() + duration)).into(),
+		}
+	}
+
+	/// Equivalent to [`async_io::Timer::at`], a timer that fires once at `instant`.
+	pub fn at(&self, instant: Instant) -> Timer {
+		match (self, instant) {
+			(MockableClock::Real, Instant::Real(instant)) => async_io::Timer::at(instant).into(),
+			#[cfg(feature = "mock")]
+			(MockableClock::Mock(registry), Instant::Mock(instant)) => {
+				timer::MockOneShot::new(registry.clone(), Some(instant)).into()
 			}
+			#[cfg(feature = "mock")]
+			_ => panic!("Clock and instant weren't compatible, both need to be either real or mocked"),
 		}
 	}
 
@@ -107,5 +209,48 @@ impl MockableClock {
 		}
 	}
 
-	// AFAIK smol doesn't have any timeout functionality
+	pub fn timeout<T>(&self, duration: Duration, future: T) -> Timeout<T>
+	where
+		T: Future,
+	{
+		use MockableClock::*;
+		match self {
+			Real => Timeout::real(future, async_io::Timer::after(duration)),
+			#[cfg(feature = "mock")]
+			Mock(registry) => registry.timeout(duration, future).into(),
+		}
+	}
+
+	pub fn timeout_at<T>(&self, deadline: Instant, future: T) -> Timeout<T>
+	where
+		T: Future,
+	{
+		match (self, deadline) {
+			(MockableClock::Real, Instant::Real(deadline)) => Timeout::real(future, async_io::Timer::at(deadline)),
+			#[cfg(feature = "mock")]
+			(MockableClock::Mock(registry), Instant::Mock(deadline)) => registry.timeout_at(deadline, future).into(),
+			#[cfg(feature = "mock")]
+			_ => panic!("Clock and instant weren't compatible, both need to be either real or mocked"),
+		}
+	}
+}
+
+fn real_deadline_after(duration: Duration) -> std::time::Instant {
+	let now = std::time::Instant::now();
+	// like tokio, durations that are too long to be represented sleep for roughly 30 years instead
+	let far_future = || now + Duration::from_secs(30 * 365 * 24 * 60 * 60);
+	now.checked_add(duration).unwrap_or_else(far_future)
+}
+
+/// Formats [`MockableClock::system_time`], so log timestamps show the mocked time when the clock is mocked.
+#[cfg(feature = "tracing-subscriber")]
+impl tracing_subscriber::fmt::time::FormatTime for MockableClock {
+	fn format_time(&self, writer: &mut tracing_subscriber::fmt::format::Writer<'_>) -> std::fmt::Result {
+		use MockableClock::*;
+		match self {
+			Real => tracing_subscriber::fmt::time::SystemTime.format_time(writer),
+			#[cfg(feature = "mock")]
+			Mock(registry) => registry.format_time(writer),
+		}
+	}
 }
@@ -0,0 +1,40 @@
+use crate::{MockableClock, TimeHandlerGuard};
+use async_time_mock_core::CronSchedule;
+use std::time::SystemTime;
+
+/// Ticks whenever the system time matches a [`CronSchedule`], see [`MockableClock::schedule`].
+pub struct Schedule {
+	clock: MockableClock,
+	cron_schedule: CronSchedule,
+	last_tick: Option<SystemTime>,
+}
+
+impl Schedule {
+	pub(crate) fn new(clock: MockableClock, cron_schedule: CronSchedule) -> Self {
+		Self {
+			clock,
+			cron_schedule,
+			last_tick: None,
+		}
+	}
+
+	/// Waits until the system time reaches the next time matching the schedule and returns that time.
+	///
+	/// Matching times that have passed while the previous tick was processed are skipped.
+	/// Never finishes if there is no further matching time.
+	pub async fn tick(&mut self) -> (TimeHandlerGuard, SystemTime) {
+		let now = self.clock.system_time();
+		let after = self.last_tick.map_or(now, |last_tick| last_tick.max(now));
+		let Some(next_tick) = self.cron_schedule.next_after(after) else {
+			return std::future::pending().await;
+		};
+
+		let guard = self.clock.sleep_until_system_time(next_tick).await;
+		self.last_tick = Some(next_tick);
+		(guard, next_tick)
+	}
+
+	pub fn cron_schedule(&self) -> &CronSchedule {
+		&self.cron_schedule
+	}
+}
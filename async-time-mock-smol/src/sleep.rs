@@ -0,0 +1,93 @@
+use crate::{Instant, TimeHandlerGuard};
+#[cfg(feature = "mock")]
+use async_time_mock_core::TimerListener;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+/// Future returned by [`MockableClock::sleep`](crate::MockableClock::sleep),
+/// [`MockableClock::sleep_until`](crate::MockableClock::sleep_until)
+/// and [`MockableClock::sleep_until_system_time`](crate::MockableClock::sleep_until_system_time).
+#[derive(Debug)]
+pub enum Sleep {
+	Real {
+		timer: async_io::Timer,
+		deadline: std::time::Instant,
+	},
+	#[cfg(feature = "mock")]
+	Mock(TimerListener),
+}
+
+impl Sleep {
+	pub(crate) fn real(deadline: std::time::Instant) -> Self {
+		Self::Real {
+			timer: async_io::Timer::at(deadline),
+			deadline,
+		}
+	}
+
+	/// Point in time the sleep finishes at.
+	pub fn deadline(&self) -> Instant {
+		use Sleep::*;
+		match self {
+			Real { deadline, .. } => (*deadline).into(),
+			#[cfg(feature = "mock")]
+			Mock(listener) => listener.deadline().into(),
+		}
+	}
+
+	/// Whether the sleep has finished. When mocked, this is only the case once the timer has been triggered
+	/// by advancing the time, not as soon as the [`deadline`](Self::deadline) has been reached.
+	pub fn is_elapsed(&self) -> bool {
+		use Sleep::*;
+		match self {
+			Real { deadline, .. } => std::time::Instant::now() >= *deadline,
+			#[cfg(feature = "mock")]
+			Mock(listener) => listener.is_elapsed(),
+		}
+	}
+
+	/// Resets the sleep to finish at `deadline` instead, even if it has already finished.
+	///
+	/// # Panics
+	/// If the sleep and `deadline` aren't either both mock or both real.
+	pub fn reset(&mut self, deadline: Instant) {
+		match (self, deadline) {
+			(Sleep::Real { timer, deadline }, Instant::Real(new_deadline)) => {
+				timer.set_at(new_deadline);
+				*deadline = new_deadline;
+			}
+			#[cfg(feature = "mock")]
+			(Sleep::Mock(listener), Instant::Mock(deadline)) => Pin::new(listener).reset(deadline),
+			#[cfg(feature = "mock")]
+			_ => panic!("Sleep and deadline weren't compatible, both need to be either real or mocked"),
+		}
+	}
+}
+
+impl Future for Sleep {
+	type Output = TimeHandlerGuard;
+
+	fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = self.get_mut();
+		use Sleep::*;
+		match this {
+			Real { timer, .. } => {
+				ready!(Pin::new(timer).poll(context));
+				Poll::Ready(TimeHandlerGuard::Real)
+			}
+			#[cfg(feature = "mock")]
+			Mock(listener) => {
+				let guard = ready!(Pin::new(listener).poll(context));
+				Poll::Ready(guard.into())
+			}
+		}
+	}
+}
+
+#[cfg(feature = "mock")]
+impl From<TimerListener> for Sleep {
+	fn from(listener: TimerListener) -> Self {
+		Self::Mock(listener)
+	}
+}
@@ -2,13 +2,68 @@ use crate::{Instant, TimeHandlerGuard};
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum Timer {
 	Real(async_io::Timer),
 	#[cfg(feature = "mock")]
 	MockInterval(async_time_mock_core::Interval),
-	// TODO: sleep, if we ever want to support the Timer methods below
+	#[cfg(feature = "mock")]
+	MockOneShot(MockOneShot),
+}
+
+/// Mocked equivalent of an [`async_io::Timer`] that fires at most once,
+/// created by [`MockableClock::after`](crate::MockableClock::after), [`MockableClock::at`](crate::MockableClock::at)
+/// and [`MockableClock::never`](crate::MockableClock::never).
+#[cfg(feature = "mock")]
+pub struct MockOneShot {
+	timer_registry: std::sync::Arc<async_time_mock_core::TimerRegistry>,
+	/// `None` once the timer has fired or if it never fires.
+	scheduled: Option<(
+		async_time_mock_core::Instant,
+		Pin<Box<async_time_mock_core::TimerListener>>,
+	)>,
+}
+
+#[cfg(feature = "mock")]
+impl MockOneShot {
+	pub(crate) fn new(
+		timer_registry: std::sync::Arc<async_time_mock_core::TimerRegistry>,
+		deadline: Option<async_time_mock_core::Instant>,
+	) -> Self {
+		let scheduled = deadline.map(|deadline| (deadline, Box::pin(timer_registry.sleep_until(deadline))));
+		Self {
+			timer_registry,
+			scheduled,
+		}
+	}
+
+	fn poll_fire(&mut self, context: &mut Context<'_>) -> Poll<(TimeHandlerGuard, Instant)> {
+		let Some((deadline, listener)) = &mut self.scheduled else {
+			// same as async-io, a timer that has fired stays pending forever
+			return Poll::Pending;
+		};
+		let deadline = *deadline;
+		let guard = std::task::ready!(listener.as_mut().poll(context));
+		self.scheduled = None;
+		Poll::Ready((guard.into(), deadline.into()))
+	}
+}
+
+#[cfg(feature = "mock")]
+impl std::fmt::Debug for MockOneShot {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let Self {
+			timer_registry,
+			scheduled,
+		} = self;
+		formatter
+			.debug_struct("MockOneShot")
+			.field("timer_registry", timer_registry)
+			.field("deadline", &scheduled.as_ref().map(|(deadline, _)| deadline))
+			.finish()
+	}
 }
 
 impl From<async_io::Timer> for Timer {
@@ -24,17 +79,127 @@ impl From<async_time_mock_core::Interval> for Timer {
 	}
 }
 
+#[cfg(feature = "mock")]
+impl From<MockOneShot> for Timer {
+	fn from(one_shot: MockOneShot) -> Self {
+		Self::MockOneShot(one_shot)
+	}
+}
+
 impl Timer {
-	// Timer::never can't determine if it should real or mock, therefore omitted
-
-	// Timer::after isn't supported because it would require a TimerRegistry
-	// Timer::at isn't supported because it would require a TimerRegistry
-	// Timer::interval isn't supported because it would require a TimerRegistry
-	// Timer::interval_at isn't supported because it would require a TimerRegistry
-	// Timer::set_after isn't implemented and is unsure if will be
-	// Timer::set_at isn't implemented and is unsure if will be
-	// Timer::set_interval isn't implemented and is unsure if will be
-	// Timer::set_interval_at isn't implemented and is unsure if will be
+	/// Point in time the next tick of a mocked timer is scheduled at, see [`async_time_mock_core::Interval::next_deadline`].
+	/// `None` for mocked one-shot timers that have already fired or never fire.
+	/// Always `None` for real timers because async-io doesn't expose it.
+	pub fn next_deadline(&self) -> Option<Instant> {
+		use Timer::*;
+		match self {
+			Real(_) => None,
+			#[cfg(feature = "mock")]
+			MockInterval(interval) => Some(interval.next_deadline().into()),
+			#[cfg(feature = "mock")]
+			MockOneShot(one_shot) => one_shot.scheduled.as_ref().map(|(deadline, _)| (*deadline).into()),
+		}
+	}
+
+	/// Number of ticks of a mocked interval that didn't happen at their deadline,
+	/// see [`async_time_mock_core::Interval::missed_ticks`].
+	/// Always `None` for one-shot timers and for real timers because async-io doesn't expose it.
+	pub fn missed_ticks(&self) -> Option<u64> {
+		use Timer::*;
+		match self {
+			Real(_) => None,
+			#[cfg(feature = "mock")]
+			MockInterval(interval) => Some(interval.missed_ticks()),
+			#[cfg(feature = "mock")]
+			MockOneShot(_) => None,
+		}
+	}
+
+	// Timer::never, Timer::after, Timer::at, Timer::interval and Timer::interval_at can't determine
+	// if they should be real or mocked, use the equivalent methods of MockableClock instead
+
+	/// Equivalent to [`async_io::Timer::will_fire`].
+	pub fn will_fire(&self) -> bool {
+		use Timer::*;
+		match self {
+			Real(timer) => timer.will_fire(),
+			#[cfg(feature = "mock")]
+			MockInterval(_) => true,
+			#[cfg(feature = "mock")]
+			MockOneShot(one_shot) => one_shot.scheduled.is_some(),
+		}
+	}
+
+	/// Equivalent to [`async_io::Timer::set_after`], turns the timer into a one-shot timer.
+	pub fn set_after(&mut self, duration: Duration) {
+		use Timer::*;
+		match self {
+			Real(timer) => timer.set_after(duration),
+			#[cfg(feature = "mock")]
+			MockInterval(_) | MockOneShot(_) => {
+				let timer_registry = self.mock_timer_registry();
+				let deadline = timer_registry.now() + duration;
+				*self = self::MockOneShot::new(timer_registry, Some(deadline)).into();
+			}
+		}
+	}
+
+	/// Equivalent to [`async_io::Timer::set_at`], turns the timer into a one-shot timer.
+	///
+	/// # Panics
+	/// If the timer and `instant` aren't either both mock or both real.
+	pub fn set_at(&mut self, instant: Instant) {
+		match (&mut *self, instant) {
+			(Timer::Real(timer), Instant::Real(instant)) => timer.set_at(instant),
+			#[cfg(feature = "mock")]
+			(Timer::MockInterval(_) | Timer::MockOneShot(_), Instant::Mock(instant)) => {
+				*self = MockOneShot::new(self.mock_timer_registry(), Some(instant)).into();
+			}
+			#[cfg(feature = "mock")]
+			_ => panic!("Timer and instant weren't compatible, both need to be either real or mocked"),
+		}
+	}
+
+	/// Equivalent to [`async_io::Timer::set_interval`], turns the timer into an interval.
+	pub fn set_interval(&mut self, period: Duration) {
+		use Timer::*;
+		match self {
+			Real(timer) => timer.set_interval(period),
+			#[cfg(feature = "mock")]
+			MockInterval(_) | MockOneShot(_) => {
+				let timer_registry = self.mock_timer_registry();
+				// like async-io, the first tick is one period from now
+				let start = timer_registry.now() + period;
+				*self = timer_registry.interval_at(start, period).into();
+			}
+		}
+	}
+
+	/// Equivalent to [`async_io::Timer::set_interval_at`], turns the timer into an interval.
+	///
+	/// # Panics
+	/// If the timer and `start` aren't either both mock or both real.
+	pub fn set_interval_at(&mut self, start: Instant, period: Duration) {
+		match (&mut *self, start) {
+			(Timer::Real(timer), Instant::Real(start)) => timer.set_interval_at(start, period),
+			#[cfg(feature = "mock")]
+			(Timer::MockInterval(_) | Timer::MockOneShot(_), Instant::Mock(start)) => {
+				*self = self.mock_timer_registry().interval_at(start, period).into();
+			}
+			#[cfg(feature = "mock")]
+			_ => panic!("Timer and instant weren't compatible, both need to be either real or mocked"),
+		}
+	}
+
+	#[cfg(feature = "mock")]
+	fn mock_timer_registry(&self) -> std::sync::Arc<async_time_mock_core::TimerRegistry> {
+		use Timer::*;
+		match self {
+			Real(_) => unreachable!("Real timers don't have a TimerRegistry"),
+			MockInterval(interval) => interval.timer_registry().clone(),
+			MockOneShot(one_shot) => one_shot.timer_registry.clone(),
+		}
+	}
 }
 
 impl Future for Timer {
@@ -51,6 +216,8 @@ impl Future for Timer {
 			MockInterval(interval) => interval
 				.poll_tick(context)
 				.map(|(guard, instant)| (guard.into(), instant.into())),
+			#[cfg(feature = "mock")]
+			MockOneShot(one_shot) => one_shot.poll_fire(context),
 		}
 	}
 }
@@ -70,6 +237,8 @@ impl futures_core::Stream for Timer {
 			MockInterval(interval) => interval
 				.poll_tick(context)
 				.map(|(guard, instant)| Some((guard.into(), instant.into()))),
+			#[cfg(feature = "mock")]
+			MockOneShot(one_shot) => one_shot.poll_fire(context).map(Some),
 		}
 	}
 
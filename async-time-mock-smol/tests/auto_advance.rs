@@ -0,0 +1,26 @@
+#![cfg(feature = "mock")]
+use async_time_mock_smol::MockableClock;
+use std::time::Duration;
+
+#[test]
+fn auto_advance_should_advance_the_mocked_time_once_the_tasks_are_idle() {
+	smol::block_on(async {
+		let (clock, timer_registry) = MockableClock::mock();
+		let start = timer_registry.now();
+		let auto_advance = smol::spawn({
+			let clock = clock.clone();
+			async move { clock.auto_advance().await }
+		});
+
+		drop(clock.sleep(Duration::from_secs(60 * 60)).await);
+
+		assert_eq!(Duration::from_secs(60 * 60), timer_registry.now().duration_since(start));
+		drop(auto_advance);
+	});
+}
+
+#[test]
+#[should_panic = "Only a mocked clock can be advanced"]
+fn auto_advance_should_panic_with_a_real_clock() {
+	smol::block_on(MockableClock::Real.auto_advance());
+}
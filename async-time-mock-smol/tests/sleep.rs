@@ -0,0 +1,57 @@
+#![cfg(feature = "mock")]
+use async_time_mock_smol::{Instant, MockableClock};
+use smol::future::zip;
+use std::time::Duration;
+
+#[test]
+fn sleep_should_expose_its_deadline() {
+	let (clock, timer_registry) = MockableClock::mock();
+
+	let sleep = clock.sleep(Duration::from_secs(10));
+
+	assert_eq!(
+		Instant::from(timer_registry.now() + Duration::from_secs(10)),
+		sleep.deadline()
+	);
+	assert!(!sleep.is_elapsed());
+}
+
+#[test]
+fn reset_should_reschedule_the_sleep() {
+	smol::block_on(async {
+		let (clock, timer_registry) = MockableClock::mock();
+		let start = timer_registry.now();
+		let mut sleep = clock.sleep(Duration::from_secs(10));
+
+		sleep.reset((start + Duration::from_secs(20)).into());
+
+		timer_registry.advance_time(Duration::from_secs(10)).await;
+		assert!(!sleep.is_elapsed());
+		assert_eq!(1, timer_registry.pending_timer_count());
+
+		zip(timer_registry.advance_time(Duration::from_secs(10)), async {
+			drop((&mut sleep).await);
+		})
+		.await;
+		assert!(sleep.is_elapsed());
+		assert_eq!(Instant::from(start + Duration::from_secs(20)), sleep.deadline());
+	});
+}
+
+#[test]
+fn sleep_until_system_time_should_follow_jumps_of_the_system_time() {
+	smol::block_on(async {
+		let (clock, timer_registry) = MockableClock::mock();
+		let until = clock.system_time() + Duration::from_secs(60 * 60);
+		let mut sleep = clock.sleep_until_system_time(until);
+
+		timer_registry.jump_system_time_forward(Duration::from_secs(2 * 60 * 60));
+		zip(timer_registry.advance_time(Duration::from_secs(1)), async {
+			drop((&mut sleep).await);
+		})
+		.await;
+
+		assert!(sleep.is_elapsed());
+		assert_eq!(0, timer_registry.pending_timer_count());
+	});
+}
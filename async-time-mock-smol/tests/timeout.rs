@@ -0,0 +1,70 @@
+use async_time_mock_smol::{Elapsed, MockableClock};
+use std::future;
+use std::time::Duration;
+
+#[test]
+fn real_timeout_should_elapse_if_the_future_takes_too_long() {
+	smol::block_on(async {
+		let clock = MockableClock::Real;
+
+		let result = clock.timeout(Duration::from_millis(1), future::pending::<()>()).await;
+
+		assert!(matches!(result, Err(Elapsed::Real)));
+	});
+}
+
+#[test]
+fn real_timeout_should_return_the_output_if_the_future_finishes_in_time() {
+	smol::block_on(async {
+		let clock = MockableClock::Real;
+
+		let result = clock.timeout(Duration::from_secs(60 * 60), future::ready(42)).await;
+
+		assert_eq!(42, result.expect("Timeout expired"));
+	});
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn mock_timeout_should_hold_back_advancing_the_time_until_elapsed_is_dropped() {
+	use smol::future::{yield_now, zip};
+	use std::sync::atomic::{AtomicBool, Ordering};
+
+	smol::block_on(async {
+		let (clock, timer_registry) = MockableClock::mock();
+		let timeout = clock.timeout(Duration::from_secs(10), future::pending::<()>());
+		let handled = AtomicBool::new(false);
+
+		zip(
+			async {
+				timer_registry.advance_time(Duration::from_secs(10)).await;
+				assert!(
+					handled.load(Ordering::SeqCst),
+					"Time was advanced before the timeout was handled"
+				);
+			},
+			async {
+				let elapsed = timeout.await.expect_err("Future can't finish");
+				assert!(matches!(elapsed, Elapsed::Mock(_)));
+				for _ in 0..10 {
+					yield_now().await;
+				}
+				handled.store(true, Ordering::SeqCst);
+				drop(elapsed);
+			},
+		)
+		.await;
+	});
+}
+
+#[cfg(feature = "mock")]
+#[test]
+#[should_panic = "Clock and instant weren't compatible, both need to be either real or mocked"]
+fn timeout_at_should_panic_with_a_mocked_deadline_on_a_real_clock() {
+	use async_time_mock_smol::Instant;
+
+	let (_, timer_registry) = MockableClock::mock();
+	let deadline = Instant::from(timer_registry.now() + Duration::from_secs(10));
+
+	let _timeout = MockableClock::Real.timeout_at(deadline, future::pending::<()>());
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "mock")]
+use async_time_mock_smol::{Instant, MockableClock};
+use smol::future::{poll_once, zip};
+use std::time::Duration;
+
+#[test]
+fn one_shot_timer_should_fire_once_and_can_be_rearmed() {
+	smol::block_on(async {
+		let (clock, timer_registry) = MockableClock::mock();
+		let start = timer_registry.now();
+		let mut timer = clock.after(Duration::from_secs(10));
+		assert_eq!(Some((start + Duration::from_secs(10)).into()), timer.next_deadline());
+
+		let ((), (_, fired_at)) = zip(timer_registry.advance_time(Duration::from_secs(10)), async {
+			let (guard, fired_at) = (&mut timer).await;
+			drop(guard);
+			((), fired_at)
+		})
+		.await;
+		assert_eq!(Instant::from(start + Duration::from_secs(10)), fired_at);
+		assert!(!timer.will_fire());
+		assert!(
+			poll_once(&mut timer).await.is_none(),
+			"Timer should have stayed pending after firing once"
+		);
+
+		timer.set_after(Duration::from_secs(5));
+		assert!(timer.will_fire());
+		let ((), (_, fired_at)) = zip(timer_registry.advance_time(Duration::from_secs(5)), async {
+			let (guard, fired_at) = (&mut timer).await;
+			drop(guard);
+			((), fired_at)
+		})
+		.await;
+		assert_eq!(Instant::from(start + Duration::from_secs(15)), fired_at);
+	});
+}
+
+#[test]
+fn never_should_not_schedule_a_timer() {
+	let (clock, timer_registry) = MockableClock::mock();
+
+	let timer = clock.never();
+
+	assert!(!timer.will_fire());
+	assert_eq!(None, timer.next_deadline());
+	assert_eq!(0, timer_registry.pending_timer_count());
+}
+
+#[test]
+fn set_interval_should_turn_a_one_shot_timer_into_an_interval() {
+	let (clock, timer_registry) = MockableClock::mock();
+	let start = timer_registry.now();
+	let mut timer = clock.after(Duration::from_secs(10));
+
+	timer.set_interval(Duration::from_secs(3));
+
+	assert_eq!(Some((start + Duration::from_secs(3)).into()), timer.next_deadline());
+	assert_eq!(1, timer_registry.pending_timer_count());
+}
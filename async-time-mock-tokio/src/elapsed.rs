@@ -5,7 +5,8 @@ use std::fmt::{Debug, Display, Formatter};
 #[must_use = "Elapsed must only be dropped once all side-effects of the timeout have been handled."]
 #[derive(Debug)]
 pub enum Elapsed {
-	Real(tokio::time::error::Elapsed),
+	/// tokio's own error type can't be constructed outside of tokio.
+	Real,
 	#[cfg(feature = "mock")]
 	Mock(async_time_mock_core::Elapsed),
 }
@@ -14,7 +15,7 @@ impl Display for Elapsed {
 	fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
 		use Elapsed::*;
 		match self {
-			Real(error) => Display::fmt(error, formatter),
+			Real => formatter.write_str("deadline has elapsed"),
 			#[cfg(feature = "mock")]
 			Mock(elapsed) => Display::fmt(elapsed, formatter),
 		}
@@ -24,8 +25,8 @@ impl Display for Elapsed {
 impl Error for Elapsed {}
 
 impl From<tokio::time::error::Elapsed> for Elapsed {
-	fn from(error: tokio::time::error::Elapsed) -> Self {
-		Self::Real(error)
+	fn from(_: tokio::time::error::Elapsed) -> Self {
+		Self::Real
 	}
 }
 
@@ -62,14 +62,81 @@ impl Interval {
 		}
 	}
 
-	/// NOTE: Mock timers can never miss a tick, therefore the MissedTickBehavior is only relevant for
-	/// the real timer.
+	pub fn reset_immediately(&mut self) {
+		use Interval::*;
+		match self {
+			Real(interval) => interval.reset_immediately(),
+			#[cfg(feature = "mock")]
+			Mock(interval) => interval.reset_immediately(),
+		}
+	}
+
+	pub fn reset_after(&mut self, after: Duration) {
+		use Interval::*;
+		match self {
+			Real(interval) => interval.reset_after(after),
+			#[cfg(feature = "mock")]
+			Mock(interval) => interval.reset_after(after),
+		}
+	}
+
+	pub fn reset_at(&mut self, deadline: Instant) {
+		match (self, deadline) {
+			(Interval::Real(interval), Instant::Real(deadline)) => interval.reset_at(deadline),
+			#[cfg(feature = "mock")]
+			(Interval::Mock(interval), Instant::Mock(deadline)) => interval.reset_at(deadline),
+			#[cfg(feature = "mock")]
+			_ => panic!("Interval and deadline weren't compatible, both need to be either real or mocked"),
+		}
+	}
+
+	/// NOTE: Mock timers only miss ticks if they are scheduled in the past,
+	/// see [`async_time_mock_core::MissedTickBehavior`].
 	pub fn set_missed_tick_behavior(&mut self, missed_tick_behavior: MissedTickBehavior) {
 		use Interval::*;
 		match self {
 			Real(interval) => interval.set_missed_tick_behavior(missed_tick_behavior),
 			#[cfg(feature = "mock")]
-			Mock(_) => {}
+			Mock(interval) => interval.set_missed_tick_behavior(match missed_tick_behavior {
+				MissedTickBehavior::Burst => async_time_mock_core::MissedTickBehavior::Burst,
+				MissedTickBehavior::Delay => async_time_mock_core::MissedTickBehavior::Delay,
+				MissedTickBehavior::Skip => async_time_mock_core::MissedTickBehavior::Skip,
+			}),
+		}
+	}
+
+	pub fn missed_tick_behavior(&self) -> MissedTickBehavior {
+		use Interval::*;
+		match self {
+			Real(interval) => interval.missed_tick_behavior(),
+			#[cfg(feature = "mock")]
+			Mock(interval) => match interval.missed_tick_behavior() {
+				async_time_mock_core::MissedTickBehavior::Burst => MissedTickBehavior::Burst,
+				async_time_mock_core::MissedTickBehavior::Delay => MissedTickBehavior::Delay,
+				async_time_mock_core::MissedTickBehavior::Skip => MissedTickBehavior::Skip,
+			},
+		}
+	}
+
+	/// Point in time the next tick is scheduled at, see [`async_time_mock_core::Interval::next_deadline`].
+	/// Always `None` for real intervals because tokio doesn't expose it.
+	pub fn next_deadline(&self) -> Option<Instant> {
+		use Interval::*;
+		match self {
+			Real(_) => None,
+			#[cfg(feature = "mock")]
+			Mock(interval) => Some(interval.next_deadline().into()),
+		}
+	}
+
+	/// Number of ticks that didn't happen at their deadline, see [`async_time_mock_core::Interval::missed_ticks`].
+	/// Always `None` for real intervals because tokio doesn't expose it.
+	pub fn missed_ticks(&self) -> Option<u64> {
+		use Interval::*;
+		match self {
+			Real(_) => None,
+			#[cfg(feature = "mock")]
+			Mock(interval) => Some(interval.missed_ticks()),
 		}
 	}
 
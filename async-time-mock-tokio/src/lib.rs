@@ -4,15 +4,22 @@ use std::time::{Duration, SystemTime};
 
 #[cfg(feature = "mock")]
 pub use async_time_mock_core as core;
+#[cfg(feature = "schedule")]
+pub use async_time_mock_core::{CronSchedule, InvalidCronSchedule};
 
 mod elapsed;
 mod instant;
 mod interval;
+#[cfg(feature = "schedule")]
+mod schedule;
 mod sleep;
 mod timeout;
 
+pub use elapsed::Elapsed;
 pub use instant::Instant;
 pub use interval::Interval;
+#[cfg(feature = "schedule")]
+pub use schedule::Schedule;
 pub use sleep::Sleep;
 pub use timeout::Timeout;
 
@@ -37,12 +44,51 @@ impl From<async_time_mock_core::TimeHandlerGuard> for TimeHandlerGuard {
 }
 
 impl MockableClock {
+	/// Creates a mocked clock and the [`TimerRegistry`](async_time_mock_core::TimerRegistry) to control it with.
+	/// The mocked time starts at the defaults of [`TimerRegistryBuilder`](async_time_mock_core::TimerRegistryBuilder),
+	/// use [`mock_with`](Self::mock_with) to e.g. start at a non-zero offset so instants before the start can be represented.
 	#[cfg(feature = "mock")]
 	pub fn mock() -> (Self, std::sync::Arc<async_time_mock_core::TimerRegistry>) {
 		let timer_registry = std::sync::Arc::new(async_time_mock_core::TimerRegistry::default());
 		(Self::Mock(timer_registry.clone()), timer_registry)
 	}
 
+	/// Same as [`mock`](Self::mock), but with a [`TimerRegistry`](async_time_mock_core::TimerRegistry)
+	/// configured by `builder`, e.g. to start at a different system time.
+	#[cfg(feature = "mock")]
+	pub fn mock_with(
+		builder: async_time_mock_core::TimerRegistryBuilder,
+	) -> (Self, std::sync::Arc<async_time_mock_core::TimerRegistry>) {
+		let timer_registry = std::sync::Arc::new(builder.build());
+		(Self::Mock(timer_registry.clone()), timer_registry)
+	}
+
+	/// Advances the mocked time to `until`, see [`async_time_mock_core::TimerRegistry::advance_to`].
+	///
+	/// # Panics
+	/// If the clock isn't mocked or `until` wasn't created by this clock.
+	#[cfg(feature = "mock")]
+	pub async fn advance_to(&self, until: Instant) {
+		match (self, until) {
+			(MockableClock::Mock(registry), Instant::Mock(until)) => registry.advance_to(until).await,
+			_ => panic!("Only a mocked clock can be advanced and only to a mocked instant"),
+		}
+	}
+
+	/// Advances the mocked time to the next timer whenever the other tasks look idle,
+	/// see [`async_time_mock_core::TimerRegistry::auto_advance`] for how idle tasks are detected and its limits.
+	/// This never finishes, so spawn it as a separate task (e.g. with `tokio::spawn`) and drop it once done.
+	///
+	/// # Panics
+	/// If the clock isn't mocked.
+	#[cfg(feature = "auto-advance")]
+	pub async fn auto_advance(&self) -> ! {
+		match self {
+			MockableClock::Mock(registry) => registry.auto_advance(tokio::task::yield_now).await,
+			MockableClock::Real => panic!("Only a mocked clock can be advanced"),
+		}
+	}
+
 	pub fn now(&self) -> Instant {
 		use MockableClock::*;
 		match self {
@@ -80,6 +126,28 @@ impl MockableClock {
 		}
 	}
 
+	/// Sleeps until the [`system_time`](Self::system_time) reaches `until`.
+	///
+	/// When mocked, this follows jumps of the mocked system time, see
+	/// [`async_time_mock_core::TimerRegistry::sleep_until_system_time`]. The real clock only computes how long to sleep
+	/// once and won't notice if the system time jumps in the meantime.
+	pub fn sleep_until_system_time(&self, until: SystemTime) -> Sleep {
+		use MockableClock::*;
+		match self {
+			Real => tokio::time::sleep(until.duration_since(SystemTime::now()).unwrap_or_default()).into(),
+			#[cfg(feature = "mock")]
+			Mock(registry) => registry.sleep_until_system_time(until).into(),
+		}
+	}
+
+	/// Ticks whenever the [`system_time`](Self::system_time) matches `cron_schedule`,
+	/// e.g. `clock.schedule("0 */5 * * * *".parse()?)` for every five minutes.
+	/// When mocked, the ticks follow jumps of the mocked system time.
+	#[cfg(feature = "schedule")]
+	pub fn schedule(&self, cron_schedule: CronSchedule) -> Schedule {
+		Schedule::new(self.clone(), cron_schedule)
+	}
+
 	pub fn interval(&self, period: Duration) -> Interval {
 		use MockableClock::*;
 		match self {
@@ -105,7 +173,7 @@ impl MockableClock {
 	{
 		use MockableClock::*;
 		match self {
-			Real => tokio::time::timeout(duration, future).into(),
+			Real => Timeout::real(future, tokio::time::sleep(duration)),
 			#[cfg(feature = "mock")]
 			Mock(registry) => registry.timeout(duration, future).into(),
 		}
@@ -116,7 +184,7 @@ impl MockableClock {
 		T: Future,
 	{
 		match (self, deadline) {
-			(MockableClock::Real, Instant::Real(deadline)) => tokio::time::timeout_at(deadline, future).into(),
+			(MockableClock::Real, Instant::Real(deadline)) => Timeout::real(future, tokio::time::sleep_until(deadline)),
 			#[cfg(feature = "mock")]
 			(MockableClock::Mock(registry), Instant::Mock(deadline)) => registry.timeout_at(deadline, future).into(),
 			#[cfg(feature = "mock")]
@@ -124,3 +192,16 @@ impl MockableClock {
 		}
 	}
 }
+
+/// Formats [`MockableClock::system_time`], so log timestamps show the mocked time when the clock is mocked.
+#[cfg(feature = "tracing-subscriber")]
+impl tracing_subscriber::fmt::time::FormatTime for MockableClock {
+	fn format_time(&self, writer: &mut tracing_subscriber::fmt::format::Writer<'_>) -> std::fmt::Result {
+		use MockableClock::*;
+		match self {
+			Real => tracing_subscriber::fmt::time::SystemTime.format_time(writer),
+			#[cfg(feature = "mock")]
+			Mock(registry) => registry.format_time(writer),
+		}
+	}
+}
@@ -1,4 +1,4 @@
-use crate::TimeHandlerGuard;
+use crate::{Instant, TimeHandlerGuard};
 #[cfg(feature = "mock")]
 use async_time_mock_core::TimerListener;
 use pin_project::pin_project;
@@ -28,6 +28,42 @@ impl From<TimerListener> for Sleep {
 	}
 }
 
+impl Sleep {
+	/// Equivalent to [`tokio::time::Sleep::deadline`].
+	pub fn deadline(&self) -> Instant {
+		use Sleep::*;
+		match self {
+			Real(sleep) => sleep.deadline().into(),
+			#[cfg(feature = "mock")]
+			Mock(listener) => listener.deadline().into(),
+		}
+	}
+
+	/// Equivalent to [`tokio::time::Sleep::is_elapsed`].
+	pub fn is_elapsed(&self) -> bool {
+		use Sleep::*;
+		match self {
+			Real(sleep) => sleep.is_elapsed(),
+			#[cfg(feature = "mock")]
+			Mock(listener) => listener.is_elapsed(),
+		}
+	}
+
+	/// Equivalent to [`tokio::time::Sleep::reset`].
+	///
+	/// # Panics
+	/// If the sleep and `deadline` aren't either both mock or both real.
+	pub fn reset(self: Pin<&mut Self>, deadline: Instant) {
+		match (self.project(), deadline) {
+			(SleepProjection::Real(sleep), Instant::Real(deadline)) => sleep.reset(deadline),
+			#[cfg(feature = "mock")]
+			(SleepProjection::Mock(listener), Instant::Mock(deadline)) => listener.reset(deadline),
+			#[cfg(feature = "mock")]
+			_ => panic!("Sleep and deadline weren't compatible, both need to be either real or mocked"),
+		}
+	}
+}
+
 impl Future for Sleep {
 	type Output = TimeHandlerGuard;
 
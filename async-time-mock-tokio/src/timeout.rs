@@ -1,22 +1,37 @@
 use crate::elapsed::Elapsed;
+use crate::Instant;
 use pin_project::pin_project;
 use std::fmt::{Debug, Formatter};
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+/// Future returned by [`MockableClock::timeout`](crate::MockableClock::timeout)
+/// and [`MockableClock::timeout_at`](crate::MockableClock::timeout_at).
+///
+/// The real variant is built from a [`tokio::time::Sleep`] instead of wrapping [`tokio::time::Timeout`],
+/// because the latter can't be [`reset`](Self::reset).
 #[pin_project(project = ProjectedTimeout)]
 pub enum Timeout<T> {
-	Real(#[pin] tokio::time::Timeout<T>),
+	Real {
+		#[pin]
+		future: T,
+		#[pin]
+		sleep: tokio::time::Sleep,
+	},
 	#[cfg(feature = "mock")]
 	Mock(#[pin] async_time_mock_core::Timeout<T>),
 }
 
 impl<T> Timeout<T> {
+	pub(crate) fn real(future: T, sleep: tokio::time::Sleep) -> Self {
+		Self::Real { future, sleep }
+	}
+
 	pub fn get_ref(&self) -> &T {
 		use Timeout::*;
 		match self {
-			Real(timeout) => timeout.get_ref(),
+			Real { future, .. } => future,
 			#[cfg(feature = "mock")]
 			Mock(timeout) => timeout.get_ref(),
 		}
@@ -25,7 +40,7 @@ impl<T> Timeout<T> {
 	pub fn get_mut(&mut self) -> &mut T {
 		use Timeout::*;
 		match self {
-			Real(timeout) => timeout.get_mut(),
+			Real { future, .. } => future,
 			#[cfg(feature = "mock")]
 			Mock(timeout) => timeout.get_mut(),
 		}
@@ -34,18 +49,47 @@ impl<T> Timeout<T> {
 	pub fn into_inner(self) -> T {
 		use Timeout::*;
 		match self {
-			Real(timeout) => timeout.into_inner(),
+			Real { future, .. } => future,
 			#[cfg(feature = "mock")]
 			Mock(timeout) => timeout.into_inner(),
 		}
 	}
+
+	/// Point in time the timeout expires at.
+	pub fn deadline(&self) -> Instant {
+		use Timeout::*;
+		match self {
+			Real { sleep, .. } => sleep.deadline().into(),
+			#[cfg(feature = "mock")]
+			Mock(timeout) => timeout.deadline().into(),
+		}
+	}
+
+	/// Moves the point in time the timeout expires at to `deadline` without losing the inner future,
+	/// see [`async_time_mock_core::Timeout::reset`].
+	///
+	/// # Panics
+	/// If the timeout and `deadline` aren't either both mock or both real.
+	pub fn reset(self: Pin<&mut Self>, deadline: Instant) {
+		match (self.project(), deadline) {
+			(ProjectedTimeout::Real { sleep, .. }, Instant::Real(deadline)) => sleep.reset(deadline),
+			#[cfg(feature = "mock")]
+			(ProjectedTimeout::Mock(timeout), Instant::Mock(deadline)) => timeout.reset(deadline),
+			#[cfg(feature = "mock")]
+			_ => panic!("Timeout and deadline weren't compatible, both need to be either real or mocked"),
+		}
+	}
 }
 
 impl<T: Debug> Debug for Timeout<T> {
 	fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
 		use Timeout::*;
 		match self {
-			Real(timeout) => Debug::fmt(timeout, formatter),
+			Real { future, sleep } => formatter
+				.debug_struct("Timeout")
+				.field("future", future)
+				.field("sleep", sleep)
+				.finish(),
 			#[cfg(feature = "mock")]
 			Mock(_) => formatter.debug_struct("Mock(Timeout)").finish(),
 		}
@@ -61,19 +105,26 @@ where
 	fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
 		use ProjectedTimeout::*;
 		match self.project() {
-			Real(timeout) => timeout.poll(context).map(|result| result.map_err(Into::into)),
+			Real { future, sleep } => {
+				// The sleep is polled before the future, so it still gets the coop budget of the task if the future
+				// always exhausts it, which is what `tokio::time::Timeout` handles by polling it unconstrained.
+				// Like there, the future wins if it finishes right at the deadline.
+				let elapsed = sleep.poll(context).is_ready();
+				if let Poll::Ready(output) = future.poll(context) {
+					return Poll::Ready(Ok(output));
+				}
+				if elapsed {
+					Poll::Ready(Err(Elapsed::Real))
+				} else {
+					Poll::Pending
+				}
+			}
 			#[cfg(feature = "mock")]
 			Mock(timeout) => timeout.poll(context).map(|result| result.map_err(Into::into)),
 		}
 	}
 }
 
-impl<T> From<tokio::time::Timeout<T>> for Timeout<T> {
-	fn from(timeout: tokio::time::Timeout<T>) -> Self {
-		Self::Real(timeout)
-	}
-}
-
 #[cfg(feature = "mock")]
 impl<T> From<async_time_mock_core::Timeout<T>> for Timeout<T> {
 	fn from(timeout: async_time_mock_core::Timeout<T>) -> Self {
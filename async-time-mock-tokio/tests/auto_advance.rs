@@ -0,0 +1,24 @@
+#![cfg(feature = "auto-advance")]
+use async_time_mock_tokio::MockableClock;
+use std::time::Duration;
+
+#[tokio::test]
+async fn auto_advance_should_advance_the_mocked_time_once_the_tasks_are_idle() {
+	let (clock, timer_registry) = MockableClock::mock();
+	let start = timer_registry.now();
+	let auto_advance = tokio::spawn({
+		let clock = clock.clone();
+		async move { clock.auto_advance().await }
+	});
+
+	drop(clock.sleep(Duration::from_secs(60 * 60)).await);
+
+	assert_eq!(Duration::from_secs(60 * 60), timer_registry.now().duration_since(start));
+	auto_advance.abort();
+}
+
+#[tokio::test]
+#[should_panic = "Only a mocked clock can be advanced"]
+async fn auto_advance_should_panic_with_a_real_clock() {
+	MockableClock::Real.auto_advance().await;
+}
@@ -0,0 +1,39 @@
+#![cfg(all(feature = "mock", feature = "schedule"))]
+use async_time_mock_core::TimerRegistry;
+use async_time_mock_tokio::{CronSchedule, MockableClock};
+use std::time::{Duration, SystemTime};
+
+#[tokio::test]
+async fn schedule_should_tick_across_month_boundaries() {
+	// 2024-01-31T23:59:00Z
+	let start_system_time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_706_745_540);
+	let (clock, timer_registry) =
+		MockableClock::mock_with(TimerRegistry::builder().start_system_time(start_system_time));
+	let cron_schedule = "0 0 0 1 * *".parse::<CronSchedule>().expect("Failed to parse");
+	let mut schedule = clock.schedule(cron_schedule);
+
+	let ticks = tokio::spawn(async move {
+		let mut ticks = Vec::new();
+		for _ in 0..2 {
+			let (_guard, tick) = schedule.tick().await;
+			ticks.push(tick);
+		}
+		ticks
+	});
+
+	// 2024-02-01T00:00:00Z
+	timer_registry.advance_time(Duration::from_secs(60)).await;
+	// 2024-03-01T00:00:00Z, 2024 is a leap year
+	timer_registry
+		.advance_time(Duration::from_secs(29 * 24 * 60 * 60))
+		.await;
+
+	let ticks = ticks.await.expect("Task panicked");
+	assert_eq!(
+		vec![
+			start_system_time + Duration::from_secs(60),
+			start_system_time + Duration::from_secs(60 + 29 * 24 * 60 * 60),
+		],
+		ticks
+	);
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "mock")]
+use async_time_mock_tokio::{Instant, MockableClock};
+use std::pin::pin;
+use std::time::Duration;
+
+#[tokio::test]
+async fn reset_should_extend_an_idle_timeout() {
+	let (clock, timer_registry) = MockableClock::mock();
+	let start = timer_registry.now();
+	let mut idle_timeout = pin!(clock.sleep(Duration::from_secs(10)));
+
+	timer_registry.advance_time(Duration::from_secs(5)).await;
+	idle_timeout
+		.as_mut()
+		.reset(Instant::from(start + Duration::from_secs(15)));
+	timer_registry.advance_time(Duration::from_secs(5)).await;
+	assert!(!idle_timeout.is_elapsed());
+	assert_eq!(Instant::from(start + Duration::from_secs(15)), idle_timeout.deadline());
+
+	tokio::join!(timer_registry.advance_time(Duration::from_secs(5)), async {
+		drop(idle_timeout.as_mut().await);
+	});
+	assert!(idle_timeout.is_elapsed());
+}
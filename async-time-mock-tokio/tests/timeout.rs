@@ -0,0 +1,55 @@
+use async_time_mock_tokio::{Elapsed, MockableClock};
+use std::future;
+use std::pin::pin;
+use std::time::Duration;
+
+#[tokio::test]
+async fn real_timeout_should_expire_at_the_reset_deadline() {
+	let clock = MockableClock::Real;
+	let mut timeout = pin!(clock.timeout(Duration::from_secs(60 * 60), future::pending::<()>()));
+
+	let now = clock.now();
+	timeout.as_mut().reset(now);
+
+	assert_eq!(now, timeout.deadline());
+	assert!(matches!(timeout.await, Err(Elapsed::Real)));
+}
+
+#[tokio::test]
+async fn real_timeout_should_expire_even_if_the_future_exhausts_the_coop_budget() {
+	let clock = MockableClock::Real;
+	let exhaust_budget = async {
+		loop {
+			tokio::task::consume_budget().await;
+		}
+	};
+
+	let result = tokio::time::timeout(
+		Duration::from_secs(2),
+		clock.timeout(Duration::from_millis(20), exhaust_budget),
+	)
+	.await
+	.expect("Timeout never expired");
+
+	assert!(matches!(result, Err(Elapsed::Real)));
+}
+
+#[cfg(feature = "mock")]
+#[tokio::test]
+async fn mock_timeout_should_be_extendable_without_losing_the_future() {
+	use async_time_mock_tokio::Instant;
+
+	let (clock, timer_registry) = MockableClock::mock();
+	let start = timer_registry.now();
+	let (sender, receiver) = tokio::sync::oneshot::channel::<u8>();
+	let mut timeout = pin!(clock.timeout(Duration::from_secs(10), receiver));
+
+	timer_registry.advance_time(Duration::from_secs(5)).await;
+	// a frame has been received, so extend the deadline
+	timeout.as_mut().reset(Instant::from(start + Duration::from_secs(15)));
+	timer_registry.advance_time(Duration::from_secs(5)).await;
+	sender.send(42).expect("Receiver was dropped");
+
+	let received = timeout.await.expect("Timeout expired").expect("Sender was dropped");
+	assert_eq!(42, received);
+}